@@ -34,7 +34,7 @@ impl cli::Guest for Component {
     }
 
     // Implement the run logic. Since there are no args, just print and succeed.
-    fn run(_args: Vec<String>) -> u8 {
+    fn run_parsed(_matches: Vec<cli::CommandMatches>) -> u8 {
         // Use the imported print function from the host
         print("[minimal extension] run called, doing nothing.");
         // Return success code
@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use crate::{CanisterManifest, bindings::icp::cli::command::CommandOutput};
+use crate::CanisterManifest;
 
 #[derive(Debug, thiserror::Error)]
 pub enum BuildError {
@@ -42,13 +42,13 @@ pub trait Build {
 
 pub struct Builder {
     read_file: Box<dyn Fn(&str) -> Result<Vec<u8>, String>>,
-    execute: Box<dyn Fn(&str, &[String]) -> Result<CommandOutput, String>>,
+    execute: Box<dyn Fn(&str, &[String]) -> Result<u32, String>>,
 }
 
 impl Builder {
     pub fn new(
         read_file: Box<dyn Fn(&str) -> Result<Vec<u8>, String>>,
-        execute: Box<dyn Fn(&str, &[String]) -> Result<CommandOutput, String>>,
+        execute: Box<dyn Fn(&str, &[String]) -> Result<u32, String>>,
     ) -> Self {
         Builder { read_file, execute }
     }
@@ -100,14 +100,14 @@ impl Build for Builder {
         .map(ToString::to_string);
 
         // Invoke the `moc` command
-        let out = (self.execute)("moc", &args)
+        let exit_code = (self.execute)("moc", &args)
             .map_err(|err| BuildError::BuildFailed(format!("failed to build canister: {}", err)))?;
 
         // Check the exit code
-        if out.exit_code != 0 {
+        if exit_code != 0 {
             return Err(BuildError::BuildFailed(format!(
                 "moc failed with exit code {}",
-                out.exit_code
+                exit_code
             )));
         }
 
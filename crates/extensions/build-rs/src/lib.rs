@@ -1,5 +1,3 @@
-use clap::Command;
-
 #[allow(warnings)]
 mod bindings;
 
@@ -10,7 +8,13 @@ use bindings::{
     },
     icp::{
         build::registry::register_provider,
-        cli::{command::execute, filesystem::read_file, misc::print},
+        cli::{
+            command::{Output, SpawnOptions, Stdio, spawn},
+            environment::get_var,
+            filesystem::read_file,
+            log::{Level, log},
+            misc::print,
+        },
     },
 };
 
@@ -18,23 +22,47 @@ mod ops;
 use once_cell::sync::OnceCell;
 use ops::build::{Build, Builder};
 
-mod spec;
 use serde::Deserialize;
-use spec::CommandSpec;
 
 struct Component;
 
 thread_local! {
     static BUILDER: OnceCell<Box<dyn Build>> = OnceCell::with_value({
         let v = Builder::new(
-            Box::new(read_file),
-            Box::new(execute),
+            Box::new(|p| read_file(p).map_err(|err| err.to_string())),
+            Box::new(get_var),
+            Box::new(stream),
         );
 
         Box::new(v)
     });
 }
 
+/// Executes a command, printing its output as it is produced, and returns its exit code
+fn stream(command: &str, args: &[String]) -> Result<u32, String> {
+    let p = spawn(
+        command,
+        args,
+        &SpawnOptions {
+            cwd: None,
+            env: vec![],
+            stdin: Stdio::Null,
+            stdout: Stdio::Piped,
+            stderr: Stdio::Piped,
+        },
+    )?;
+
+    while let Some(out) = p.read() {
+        let (Output::Stdout(bs) | Output::Stderr(bs)) = out;
+        print(&format!(
+            "[build-rs] {}",
+            String::from_utf8_lossy(&bs).trim_end()
+        ));
+    }
+
+    p.wait()
+}
+
 #[derive(Deserialize, Debug)]
 struct RustProperties {
     package: String,
@@ -61,7 +89,9 @@ const CLI_SPEC: &str = r#"{
     "name": "build-rs",
     "help": "Builder for Rust canisters",
     "args": [],
-    "subcommands": []
+    "subcommands": [],
+    "commands": ["cargo"],
+    "registers": ["icp:build/registry"]
 }"#;
 
 impl init::Guest for Component {
@@ -81,17 +111,9 @@ impl cli::Guest for Component {
         CLI_SPEC.to_string()
     }
 
-    fn run(args: Vec<String>) -> u8 {
-        // Parse the CLI spec
-        let cspec: CommandSpec =
-            serde_json::from_str(CLI_SPEC).expect("invalid command-line interface");
-
-        // Convert the spec into a clap Command
-        let c: Command = cspec.into();
-
-        // Parse the command-line arguments
-        let m = c.get_matches_from(args);
-        let canister_dir = m.try_get_one::<String>("dir").unwrap().unwrap();
+    fn run_parsed(matches: Vec<cli::CommandMatches>) -> u8 {
+        // Arguments (required arguments are checked by the host)
+        let canister_dir = value(&matches[0].args, "dir").expect("missing canister directory");
 
         match BUILDER.with(|v| {
             v.get()
@@ -115,10 +137,11 @@ impl cli::Guest for Component {
 
 impl canister_build::Guest for Component {
     fn build_canister(canister_dir: String) -> Result<String, String> {
-        print(&format!(
-            "[build-rs] Received build request for canister at: {}",
-            canister_dir
-        ));
+        log(
+            Level::Debug,
+            "Received build request",
+            &[("canister".to_string(), canister_dir.clone())],
+        );
 
         BUILDER.with(|v| {
             v.get()
@@ -129,4 +152,12 @@ impl canister_build::Guest for Component {
     }
 }
 
+/// Value of an argument taking a single value, as parsed by the host
+fn value(args: &[cli::Arg], name: &str) -> Option<String> {
+    args.iter().find_map(|a| match &a.value {
+        cli::ArgValue::One(v) if a.name == name => Some(v.to_owned()),
+        _ => None,
+    })
+}
+
 bindings::export!(Component with_types_in bindings);
@@ -1,9 +1,6 @@
 use std::path::Path;
 
-use crate::{
-    CanisterManifest,
-    bindings::icp::cli::{command::CommandOutput, misc::print},
-};
+use crate::{CanisterManifest, bindings::icp::cli::misc::print};
 
 #[derive(Debug, thiserror::Error)]
 pub enum BuildError {
@@ -45,15 +42,21 @@ pub trait Build {
 
 pub struct Builder {
     read_file: Box<dyn Fn(&str) -> Result<Vec<u8>, String>>,
-    execute: Box<dyn Fn(&str, &[String]) -> Result<CommandOutput, String>>,
+    get_var: Box<dyn Fn(&str) -> Option<String>>,
+    execute: Box<dyn Fn(&str, &[String]) -> Result<u32, String>>,
 }
 
 impl Builder {
     pub fn new(
         read_file: Box<dyn Fn(&str) -> Result<Vec<u8>, String>>,
-        execute: Box<dyn Fn(&str, &[String]) -> Result<CommandOutput, String>>,
+        get_var: Box<dyn Fn(&str) -> Option<String>>,
+        execute: Box<dyn Fn(&str, &[String]) -> Result<u32, String>>,
     ) -> Self {
-        Builder { read_file, execute }
+        Builder {
+            read_file,
+            get_var,
+            execute,
+        }
     }
 }
 
@@ -96,19 +99,21 @@ impl Build for Builder {
         .map(ToString::to_string);
 
         // Invoke the `cargo` command
-        let out = (self.execute)("cargo", &args)
+        let exit_code = (self.execute)("cargo", &args)
             .map_err(|err| BuildError::BuildFailed(format!("failed to build canister: {}", err)))?;
 
         // Check the exit code
-        if out.exit_code != 0 {
+        if exit_code != 0 {
             return Err(BuildError::BuildFailed(format!(
-                "moc failed with exit code {}",
-                out.exit_code
+                "cargo failed with exit code {}",
+                exit_code
             )));
         }
 
-        // Specify output path
-        let output_path = Path::new("target")
+        // Specify output path (cargo honors CARGO_TARGET_DIR, if the extension is allowed to see it)
+        let target_dir = (self.get_var)("CARGO_TARGET_DIR").unwrap_or("target".to_string());
+
+        let output_path = Path::new(&target_dir)
             .join("wasm32-unknown-unknown")
             .join("release")
             .join(format!(
@@ -1,4 +1,3 @@
-use clap::Command;
 use dashmap::DashMap;
 use once_cell::sync::{Lazy, OnceCell};
 use serde::Deserialize;
@@ -20,9 +19,6 @@ use bindings::{
 mod ops;
 use ops::build::{Build, Builder};
 
-mod spec;
-use spec::CommandSpec;
-
 struct Component;
 
 pub type LazyRef<T> = &'static Lazy<T>;
@@ -32,7 +28,7 @@ static BUILDERS: Lazy<DashMap<String, (String, String)>> = Lazy::new(|| DashMap:
 thread_local! {
     static BUILDER: OnceCell<Box<dyn Build>> = OnceCell::with_value({
         let v = Builder::new(
-            Box::new(read_file), //
+            Box::new(|p| read_file(p).map_err(|err| err.to_string())), //
             &BUILDERS,
         );
 
@@ -74,17 +70,9 @@ impl cli::Guest for Component {
         CLI_SPEC.to_string()
     }
 
-    fn run(args: Vec<String>) -> u8 {
-        // Parse the CLI spec
-        let cspec: CommandSpec =
-            serde_json::from_str(CLI_SPEC).expect("invalid command-line interface");
-
-        // Convert the spec into a clap Command
-        let c: Command = cspec.into();
-
-        // Parse the command-line arguments
-        let m = c.get_matches_from(args);
-        let canister_dir = m.try_get_one::<String>("dir").unwrap().unwrap();
+    fn run_parsed(matches: Vec<cli::CommandMatches>) -> u8 {
+        // Arguments (required arguments are checked by the host)
+        let canister_dir = value(&matches[0].args, "dir").expect("missing canister directory");
 
         match BUILDER.with(|v| {
             v.get()
@@ -132,4 +120,12 @@ impl canister_build::Guest for Component {
     }
 }
 
+/// Value of an argument taking a single value, as parsed by the host
+fn value(args: &[cli::Arg], name: &str) -> Option<String> {
+    args.iter().find_map(|a| match &a.value {
+        cli::ArgValue::One(v) if a.name == name => Some(v.to_owned()),
+        _ => None,
+    })
+}
+
 bindings::export!(Component with_types_in bindings);
@@ -1,5 +1,3 @@
-use clap::Command;
-
 #[allow(warnings)]
 mod bindings;
 
@@ -11,9 +9,6 @@ use bindings::{
     icp::cli::misc::print,
 };
 
-mod spec;
-use spec::CommandSpec;
-
 struct Component;
 
 const CLI_SPEC: &str = r#"{
@@ -68,28 +63,21 @@ impl cli::Guest for Component {
         CLI_SPEC.to_string()
     }
 
-    fn run(args: Vec<String>) -> u8 {
-        // Parse the CLI spec
-        let cspec: CommandSpec =
-            serde_json::from_str(CLI_SPEC).expect("invalid command-line interface");
-
-        // Convert the spec into a clap Command
-        let c: Command = cspec.into();
+    fn run_parsed(matches: Vec<cli::CommandMatches>) -> u8 {
+        // Invoked subcommand
+        let subcommand = matches.get(1).map(|m| (m.name.as_str(), &m.args));
 
-        // Parse the command-line arguments
-        let m = c.get_matches_from(args);
-
-        match m.subcommand() {
+        match subcommand {
             // create
-            Some(("create", m)) => {
-                let name = m.try_get_one::<String>("name").unwrap().unwrap();
+            Some(("create", args)) => {
+                let name = value(args, "name").expect("missing identity name");
 
                 create(&name);
             }
 
             // sign
-            Some(("sign", m)) => {
-                let blob = m.try_get_one::<String>("blob").unwrap().unwrap();
+            Some(("sign", args)) => {
+                let blob = value(args, "blob").expect("missing blob");
 
                 sign(&blob);
             }
@@ -101,4 +89,12 @@ impl cli::Guest for Component {
     }
 }
 
+/// Value of an argument taking a single value, as parsed by the host
+fn value(args: &[cli::Arg], name: &str) -> Option<String> {
+    args.iter().find_map(|a| match &a.value {
+        cli::ArgValue::One(v) if a.name == name => Some(v.to_owned()),
+        _ => None,
+    })
+}
+
 bindings::export!(Component with_types_in bindings);
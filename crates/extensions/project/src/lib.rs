@@ -1,6 +1,5 @@
 use std::{thread::LocalKey, thread_local};
 
-use clap::Command;
 use once_cell::sync::OnceCell;
 use ops::list::ListError;
 use serde::Deserialize;
@@ -14,7 +13,10 @@ use bindings::{
     },
     icp::{
         build::canister_build::build_canister,
-        cli::{filesystem, misc::print},
+        cli::{
+            filesystem,
+            misc::{emit_result, print},
+        },
     },
 };
 
@@ -25,9 +27,6 @@ use ops::{
     list::{List, Lister},
 };
 
-mod spec;
-use spec::CommandSpec;
-
 pub type LocalRef<T> = &'static LocalKey<OnceCell<T>>;
 
 struct Component;
@@ -35,7 +34,7 @@ struct Component;
 thread_local! {
     static CREATOR: OnceCell<Box<dyn Create>> = OnceCell::with_value({
         let v = Creator::new(
-            Box::new(filesystem::read_file),
+            Box::new(|p| filesystem::read_file(p).map_err(|err| err.to_string())),
         );
 
         Box::new(v)
@@ -45,7 +44,7 @@ thread_local! {
 thread_local! {
     static LISTER: OnceCell<Box<dyn List>> = OnceCell::with_value({
         let v = Lister::new(
-            Box::new(filesystem::read_file),
+            Box::new(|p| filesystem::read_file(p).map_err(|err| err.to_string())),
         );
 
         Box::new(v)
@@ -86,6 +85,7 @@ struct CanisterManifest {
 }
 
 const CLI_SPEC: &str = r#"{
+    "schema": 2,
     "name": "project",
     "help": "Internet Computer Project CLI",
     "args": [],
@@ -99,11 +99,27 @@ const CLI_SPEC: &str = r#"{
         {
             "name": "build",
             "help": "Build canisters in the project",
-            "args": []
+            "long_help": "Build every canister declared in the project's icp.toml, using the builder extension registered for its type (e.g. build-rs for Rust canisters and build-mo for Motoko canisters).",
+            "examples": [
+                { "command": "icp project build", "help": "Build all canisters" },
+                { "command": "icp project build --canister backend --canister frontend", "help": "Build only some canisters" }
+            ],
+            "args": [
+                {
+                    "name": "canister",
+                    "long": "canister",
+                    "short": "c",
+                    "multiple": true,
+                    "help": "Only build the given canister (can be repeated)"
+                }
+            ]
         },
         {
             "name": "list-canisters",
             "help": "List canisters defined in the project",
+            "examples": [
+                { "command": "icp project list-canisters" }
+            ],
             "args": []
         }
     ]
@@ -120,15 +136,12 @@ impl cli::Guest for Component {
         CLI_SPEC.to_string()
     }
 
-    fn run(args: Vec<String>) -> u8 {
-        let cspec: CommandSpec =
-            serde_json::from_str(CLI_SPEC).expect("invalid command-line interface spec");
+    fn run_parsed(matches: Vec<cli::CommandMatches>) -> u8 {
+        // Invoked subcommand
+        let subcommand = matches.get(1).map(|m| (m.name.as_str(), &m.args));
 
-        let cmd: Command = cspec.into();
-        let ms = cmd.get_matches_from(args);
-
-        match ms.subcommand() {
-            Some(("create", _m)) => {
+        match subcommand {
+            Some(("create", _args)) => {
                 match CREATOR.with(|v| v.get().expect("creator not initialized").create()) {
                     // Success
                     Ok(_) => 0,
@@ -138,8 +151,16 @@ impl cli::Guest for Component {
                 }
             }
 
-            Some(("build", _m)) => {
-                match BUILDER.with(|v| v.get().expect("builder not initialized").build()) {
+            Some(("build", args)) => {
+                let only: Vec<String> = args
+                    .iter()
+                    .find_map(|a| match &a.value {
+                        cli::ArgValue::Many(vs) if a.name == "canister" => Some(vs.to_owned()),
+                        _ => None,
+                    })
+                    .unwrap_or_default();
+
+                match BUILDER.with(|v| v.get().expect("builder not initialized").build(&only)) {
                     // Success
                     Ok(_) => 0,
 
@@ -151,16 +172,28 @@ impl cli::Guest for Component {
                 }
             }
 
-            Some(("list-canisters", _m)) => {
+            Some(("list-canisters", _args)) => {
                 match LISTER.with(|v| v.get().expect("lister not initialized").list()) {
                     // Success
                     Ok(cs) => {
-                        print("Found canisters:");
-                        for canister in cs {
-                            print(&format!("  - {:?}", canister));
+                        let cs: Vec<_> = cs
+                            .into_iter()
+                            .map(|c| {
+                                serde_json::json!({
+                                    "name": c.name,
+                                    "path": c.path,
+                                    "type": c.canister_type,
+                                })
+                            })
+                            .collect();
+
+                        match emit_result(&serde_json::Value::from(cs).to_string()) {
+                            Ok(_) => 0,
+                            Err(err) => {
+                                print(&err);
+                                2
+                            }
                         }
-
-                        0
                     }
 
                     // Failure
@@ -169,11 +202,7 @@ impl cli::Guest for Component {
                         print(&err.to_string());
 
                         // Return the appropriate exit code based on the error type
-                        match err {
-                            ListError::NoCanistersFound => 3,
-                            ListError::ManifestProcessing(_) => 2,
-                            ListError::Unexpected(_) => 1,
-                        }
+                        err.into()
                     }
                 }
             }
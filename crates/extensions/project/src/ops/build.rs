@@ -1,4 +1,10 @@
-use crate::{List, ListError, LocalRef, bindings::icp::cli::misc::print};
+use crate::{
+    List, ListError, LocalRef,
+    bindings::icp::cli::{
+        log::{Level, log},
+        misc::print,
+    },
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum BuildError {
@@ -8,6 +14,9 @@ pub enum BuildError {
     #[error("One or more canisters failed to build.")]
     BuildFailed,
 
+    #[error("Canister '{0}' is not defined in the project.")]
+    UnknownCanister(String),
+
     #[error(transparent)]
     Unexpected(#[from] anyhow::Error),
 }
@@ -17,6 +26,7 @@ impl From<BuildError> for String {
         match e {
             BuildError::ListFailed(e) => e.to_string(),
             BuildError::BuildFailed => e.to_string(),
+            BuildError::UnknownCanister(_) => e.to_string(),
             BuildError::Unexpected(err) => {
                 format!("An unexpected error occurred: {}", err)
             }
@@ -29,13 +39,15 @@ impl From<BuildError> for u8 {
         match e {
             BuildError::ListFailed(e) => e.into(),
             BuildError::BuildFailed => 4,
+            BuildError::UnknownCanister(_) => 5,
             BuildError::Unexpected(_) => 2,
         }
     }
 }
 
 pub trait Build {
-    fn build(&self) -> Result<(), BuildError>;
+    /// Builds the canisters of the project, or only the given ones if any
+    fn build(&self, only: &[String]) -> Result<(), BuildError>;
 }
 
 pub struct Builder {
@@ -56,8 +68,8 @@ impl Builder {
 }
 
 impl Build for Builder {
-    fn build(&self) -> Result<(), BuildError> {
-        print("Listing canisters in project...");
+    fn build(&self, only: &[String]) -> Result<(), BuildError> {
+        log(Level::Debug, "Listing canisters in project", &[]);
 
         // List canisters
         let canisters = match self.list_canisters.with(|l| l.get().unwrap().list()) {
@@ -71,26 +83,47 @@ impl Build for Builder {
             }
         };
 
-        print(&format!("Found {} canisters to build.", canisters.len()));
+        if let Some(name) = only
+            .iter()
+            .find(|n| !canisters.iter().any(|c| c.name == **n))
+        {
+            return Err(BuildError::UnknownCanister(name.to_owned()));
+        }
+
+        let canisters: Vec<_> = canisters
+            .into_iter()
+            .filter(|c| only.is_empty() || only.contains(&c.name))
+            .collect();
+
+        log(
+            Level::Debug,
+            "Found canisters to build",
+            &[("count".to_string(), canisters.len().to_string())],
+        );
 
         let mut build_failed = false;
 
         // Iterate and build each canister
         for canister in canisters {
-            print(&format!(
-                "Attempting to build canister '{}' [{}] at path '{}'...",
-                canister.name, canister.canister_type, canister.path
-            ));
+            log(
+                Level::Info,
+                &format!("Building canister '{}'", canister.name),
+                &[
+                    ("type".to_string(), canister.canister_type.clone()),
+                    ("path".to_string(), canister.path.clone()),
+                ],
+            );
 
             match (self.build_canister)(&canister.path) {
                 Ok(_) => {
                     print(&format!("Successfully built canister '{}'.", canister.name));
                 }
                 Err(msg) => {
-                    print(&format!(
-                        "ERROR building canister '{}': {}",
-                        canister.name, msg
-                    ));
+                    log(
+                        Level::Error,
+                        &format!("Failed to build canister '{}': {}", canister.name, msg),
+                        &[],
+                    );
                     build_failed = true;
                 }
             }
@@ -4,7 +4,7 @@ use std::{env, str::FromStr};
 fn main() {
     println!("cargo:rerun-if-env-changed=DISTRIBUTION");
 
-    if let Some(distribution) = env::var("DISTRIBUTION").ok() {
+    if let Ok(distribution) = env::var("DISTRIBUTION") {
         if let Err(e) = Distribution::from_str(&distribution) {
             panic!(
                 "❌ ERROR: Invalid DISTRIBUTION value '{}': {}",
@@ -44,12 +44,12 @@ pub async fn execute_component_function(
             &mut actual_nested_results_wasm,
         )
         .await
-        .with_context(|| format!("error during Wasm function call_async",))?;
+        .context("error during Wasm function call_async")?;
 
     func_to_call
         .post_return_async(&mut store)
         .await
-        .with_context(|| format!("error during Wasm function post_return_async",))?;
+        .context("error during Wasm function post_return_async")?;
 
     // Convert the WasmVal results from the call into IcpVal
     let results_as_icp_val = actual_nested_results_wasm
@@ -59,7 +59,7 @@ pub async fn execute_component_function(
 
     // Serialize the IcpVal results into a byte vector (JSON representation)
     let results_as_bytes = serde_json::to_vec(&results_as_icp_val)
-        .with_context(|| format!("failed to serialize nested results to json",))?;
+        .context("failed to serialize nested results to json")?;
 
     // Convert the byte vector into a list of U8 WasmVals
     let results_as_wasm_u8_list = results_as_bytes
@@ -0,0 +1,277 @@
+use std::{
+    fmt::Write as _,
+    fs::{create_dir_all, write},
+    io,
+    path::{Path, PathBuf},
+};
+
+use clap::{Arg, ArgAction, Command};
+use clap_mangen::Man;
+use roff::{bold, roman, Roff};
+
+use crate::spec::Examples;
+
+/// Format of the reference pages
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Man,
+    Markdown,
+}
+
+impl Format {
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Man => "1",
+            Format::Markdown => "md",
+        }
+    }
+}
+
+/// Renders a reference page for a command and every one of its subcommands into a directory,
+/// each named after the command's full path (e.g. `icp-extension-add.1`).
+///
+/// # Returns
+///
+/// The paths of the pages, in depth-first order
+pub fn generate(mut cmd: Command, format: Format, out: &Path) -> Result<Vec<PathBuf>, io::Error> {
+    // Assigns each subcommand its full name and usage
+    cmd.build();
+
+    create_dir_all(out)?;
+
+    let mut pages = vec![];
+
+    for c in commands(&cmd) {
+        let content = match format {
+            Format::Man => man(c)?,
+            Format::Markdown => markdown(c).into_bytes(),
+        };
+
+        let p = out.join(format!("{}.{}", page_name(c), format.extension()));
+        write(&p, content)?;
+
+        pages.push(p);
+    }
+
+    Ok(pages)
+}
+
+/// Lists a command and its visible subcommands, depth-first
+fn commands(cmd: &Command) -> Vec<&Command> {
+    let mut out = vec![cmd];
+
+    for c in visible_subcommands(cmd) {
+        out.extend(commands(c));
+    }
+
+    out
+}
+
+fn visible_subcommands(cmd: &Command) -> impl Iterator<Item = &Command> {
+    cmd.get_subcommands()
+        .filter(|c| !c.is_hide_set() && c.get_name() != "help")
+}
+
+/// Arguments shown on a page, leaving out hidden ones and the generated help and version flags
+fn visible_args(cmd: &Command) -> impl Iterator<Item = &Arg> {
+    cmd.get_arguments().filter(|a| {
+        !a.is_hide_set()
+            && !matches!(
+                a.get_action(),
+                ArgAction::Help | ArgAction::HelpShort | ArgAction::HelpLong | ArgAction::Version
+            )
+    })
+}
+
+/// Full name of a command, e.g. `icp-extension-add`
+fn page_name(cmd: &Command) -> &str {
+    cmd.get_display_name().unwrap_or(cmd.get_name())
+}
+
+/// Full name of a command as typed, e.g. `icp extension add`
+fn title(cmd: &Command) -> &str {
+    cmd.get_bin_name().unwrap_or(cmd.get_name())
+}
+
+// Man
+
+fn man(cmd: &Command) -> Result<Vec<u8>, io::Error> {
+    let m = Man::new(cmd.clone());
+    let mut buf = vec![];
+
+    m.render_title(&mut buf)?;
+    m.render_name_section(&mut buf)?;
+    m.render_synopsis_section(&mut buf)?;
+    m.render_description_section(&mut buf)?;
+
+    if visible_args(cmd).next().is_some() {
+        m.render_options_section(&mut buf)?;
+    }
+
+    if visible_subcommands(cmd).next().is_some() {
+        m.render_subcommands_section(&mut buf)?;
+    }
+
+    // Examples (rendered from the spec rather than the `--help` text)
+    if let Some(Examples(examples)) = cmd.get::<Examples>() {
+        let mut roff = Roff::new();
+        roff.control("SH", ["EXAMPLES"]);
+
+        for e in examples {
+            roff.control("TP", []);
+            roff.text([bold(&e.command)]);
+
+            if let Some(help) = &e.help {
+                roff.text([roman(help)]);
+            }
+        }
+
+        roff.to_writer(&mut buf)?;
+    }
+
+    if cmd.get_version().is_some() {
+        m.render_version_section(&mut buf)?;
+    }
+
+    Ok(buf)
+}
+
+// Markdown
+
+fn markdown(cmd: &Command) -> String {
+    let mut out = String::new();
+
+    // Title
+    let _ = writeln!(out, "# {}\n", title(cmd));
+
+    // Description
+    if let Some(about) = cmd.get_long_about().or(cmd.get_about()) {
+        let _ = writeln!(out, "{about}\n");
+    }
+
+    // Version
+    if let Some(ver) = cmd.get_version() {
+        let _ = writeln!(out, "Version: `{ver}`\n");
+    }
+
+    // Usage
+    let usage = cmd.clone().render_usage().to_string();
+    let usage = usage.trim_start_matches("Usage: ");
+    let _ = writeln!(out, "## Usage\n\n```text\n{usage}\n```\n");
+
+    // Arguments
+    let (positionals, options): (Vec<_>, Vec<_>) =
+        visible_args(cmd).partition(|a| a.is_positional());
+
+    for (heading, args) in [("Arguments", positionals), ("Options", options)] {
+        if args.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(out, "## {heading}\n");
+
+        for a in args {
+            let _ = writeln!(out, "{}", markdown_arg(a));
+        }
+
+        out.push('\n');
+    }
+
+    // Subcommands
+    let subcommands: Vec<_> = visible_subcommands(cmd).collect();
+
+    if !subcommands.is_empty() {
+        let _ = writeln!(out, "## Commands\n");
+
+        for c in subcommands {
+            let _ = write!(out, "- [`{}`]({}.md)", title(c), page_name(c));
+
+            if let Some(about) = c.get_about() {
+                let _ = write!(out, ": {about}");
+            }
+
+            out.push('\n');
+        }
+
+        out.push('\n');
+    }
+
+    // Examples
+    if let Some(Examples(examples)) = cmd.get::<Examples>() {
+        let _ = writeln!(out, "## Examples\n");
+
+        for e in examples {
+            if let Some(help) = &e.help {
+                let _ = writeln!(out, "{help}:\n");
+            }
+
+            let _ = writeln!(out, "```sh\n{}\n```\n", e.command);
+        }
+    }
+
+    format!("{}\n", out.trim_end())
+}
+
+/// Renders an argument as a list item, e.g. ``- `-f, --format <FORMAT>`: Output format``
+fn markdown_arg(a: &Arg) -> String {
+    let value = match a.get_value_names() {
+        Some(names) => names.iter().map(|n| format!("<{n}>")).collect::<Vec<_>>(),
+        None if a.is_positional() => vec![format!("<{}>", a.get_id())],
+        None => vec![format!("<{}>", a.get_id().as_str().to_uppercase())],
+    }
+    .join(" ");
+
+    let mut label = vec![];
+
+    if let Some(s) = a.get_short() {
+        label.push(format!("-{s}"));
+    }
+
+    if let Some(l) = a.get_long() {
+        label.push(format!("--{l}"));
+    }
+
+    let mut label = label.join(", ");
+
+    if a.get_action().takes_values() {
+        label = match label.is_empty() {
+            true => value,
+            false => format!("{label} {value}"),
+        };
+    }
+
+    let mut item = format!("- `{label}`");
+
+    if let Some(help) = a.get_long_help().or(a.get_help()) {
+        // Continuation lines are indented to stay within the list item
+        let _ = write!(item, ": {}", help.to_string().replace('\n', "\n  "));
+    }
+
+    let mut notes = vec![];
+
+    if a.is_required_set() {
+        notes.push("required".to_owned());
+    }
+
+    let pvs = a.get_possible_values();
+    if !pvs.is_empty() {
+        let pvs: Vec<_> = pvs.iter().map(|v| format!("`{}`", v.get_name())).collect();
+        notes.push(format!("possible values: {}", pvs.join(", ")));
+    }
+
+    // Flags always default to being unset
+    let dvs = a.get_default_values();
+    if !dvs.is_empty() && a.get_action().takes_values() {
+        let dvs: Vec<_> = dvs
+            .iter()
+            .map(|v| format!("`{}`", v.to_string_lossy()))
+            .collect();
+        notes.push(format!("default: {}", dvs.join(", ")));
+    }
+
+    if !notes.is_empty() {
+        let _ = write!(item, " ({})", notes.join("; "));
+    }
+
+    item
+}
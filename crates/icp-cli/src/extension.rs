@@ -1,21 +1,40 @@
 use std::{
-    fs::{create_dir_all, read, remove_file, write},
-    path::{Path, PathBuf},
+    fs::{create_dir_all, metadata, read, remove_file, rename, write},
+    hash::{Hash, Hasher},
+    path::{absolute, Path, PathBuf},
     sync::Arc,
+    time::SystemTime,
 };
 
 use anyhow::{Context as _, Error};
 use async_trait::async_trait;
 use http::Uri;
+use icp_distribution::resolve_url;
 use reqwest::get;
+use semver::{Comparator, Op, Version, VersionReq};
+use serde::Serialize;
+use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
-use wasmtime::{component::Component, Engine};
+use wasmtime::{
+    component::{Component, Linker},
+    Engine, Store as WasmStore,
+};
 
 use icp_core::{
-    dependency::{DependencyError, DependencyGraph},
-    interface::{parse_interface_name, ComponentInterfaces, DetectIfaces, HOST_INTERFACE_PREFIX},
-    manifest::{self, Extension, Load, ManifestHandle, Store},
-    Interface,
+    dependency::{export_changes, DependencyError, DependencyGraph, InterfaceChange},
+    interface::{
+        incompatible_host_version, is_host_interface, parse_interface_name, ComponentInterfaces,
+        DetectIfaces, HOST_INTERFACE_VERSION, WASI_INTERFACE_PREFIX,
+    },
+    manifest::{self, Extension, Limits, Load, Manifest, ManifestHandle, Permissions, Store},
+    spec::{CommandSpec, SpecError},
+    Interface, Metadata, OutputFormat,
+};
+
+use crate::{
+    registry::{local_path, supported, FetchIndex, FetchIndexError},
+    tty::Terminal,
+    State,
 };
 
 enum AdditionType {
@@ -38,6 +57,130 @@ impl TryFrom<&str> for AdditionType {
     }
 }
 
+/// Retrieves the command specification of an extension by calling its `spec` export.
+///
+/// The extension is instantiated in isolation, with all of its imports defined as traps,
+/// since `spec` is not expected to interact with the host or with other extensions.
+pub async fn fetch_spec(ngn: &Engine, cmpnt: &Component) -> Result<String, Error> {
+    let mut lnk = Linker::new(ngn);
+
+    lnk.define_unknown_imports_as_traps(cmpnt)
+        .context("failed to define imports")?;
+
+    let mut store = WasmStore::new(
+        ngn,              // engine
+        State::default(), // data
+    );
+
+    store
+        .set_fuel(Limits::DEFAULT_FUEL)
+        .context("failed to set fuel")?;
+
+    let inst = crate::Extension::instantiate_async(
+        &mut store, // store
+        cmpnt,      // component
+        &lnk,       // linker
+    )
+    .await
+    .context("failed to instantiate extension")?;
+
+    let spec = inst
+        .icp_cli_cli()
+        .call_spec(&mut store)
+        .await
+        .context("failed to call spec")?;
+
+    Ok(spec)
+}
+
+/// Returns a digest of the engine's precompile compatibility hash.
+///
+/// Pre-compiled components can only be deserialized by an engine with the same hash,
+/// so a change in this value (e.g. after a wasmtime upgrade) means they must be recompiled.
+/// The digest is stored in the manifest, so it is a SHA256 of the hashed bytes rather than
+/// the output of `DefaultHasher`, whose algorithm may change between Rust releases.
+pub fn compatibility_hash(ngn: &Engine) -> String {
+    let mut h = Sha256Hasher(Sha256::new());
+    ngn.precompile_compatibility_hash().hash(&mut h);
+    format!("{:x}", h.0.finalize())
+}
+
+/// Feeds hashed values into a SHA256 digest, encoding integers as little-endian bytes
+struct Sha256Hasher(Sha256);
+
+impl Hasher for Sha256Hasher {
+    fn write(&mut self, bs: &[u8]) {
+        self.0.update(bs);
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        let d = self.0.clone().finalize();
+        u64::from_le_bytes(d[..8].try_into().expect("digest is at least 8 bytes"))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RecompileError {
+    #[error("checksum mismatch for extension {name}\nExpected: {expected}\nActual: {actual}")]
+    ChecksumMismatch {
+        name: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+/// Re-precompiles an extension from its stored component, replacing its pre-compiled component
+/// and updating its compatibility hash. The component is verified against its checksum first.
+pub fn recompile(ngn: &Engine, x: &mut Extension) -> Result<(), RecompileError> {
+    let ext = read(&x.wasm).context(format!("failed to read extension file: {:?}", x.wasm))?;
+
+    // Verify checksum
+    if let Some(expected) = &x.checksum {
+        let actual = format!("{:x}", Sha256::digest(&ext));
+        if expected != &actual {
+            return Err(RecompileError::ChecksumMismatch {
+                name: x.name.to_owned(),
+                expected: expected.to_owned(),
+                actual,
+            });
+        }
+    }
+
+    // Precompile
+    let pre = ngn
+        .precompile_component(&ext)
+        .context("failed to precompile component")?;
+
+    write(&x.pre, &pre).context("failed to write precompile to disk")?;
+
+    x.compatibility_hash = Some(compatibility_hash(ngn));
+
+    Ok(())
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum AddExtensionError {
     #[error("extension with name {0} already installed")]
@@ -49,10 +192,34 @@ pub enum AddExtensionError {
     #[error(transparent)]
     DependencyValidationFailed(#[from] DependencyError),
 
+    #[error("invalid command spec: {0}")]
+    InvalidSpec(SpecError),
+
+    #[error("extension {name} was built for icp:cli@{version}, please update it (icp supports icp:cli@{HOST_INTERFACE_VERSION})")]
+    IncompatibleHost { name: String, version: String },
+
+    #[error(
+        "extension {name} was not allowed to execute: {} (use --allow-command to allow them)",
+        commands.join(", ")
+    )]
+    CommandsNotAllowed { name: String, commands: Vec<String> },
+
+    #[error("extension {name} registers through interface {interface}, which it does not import")]
+    UnknownRegistration { name: String, interface: String },
+
     #[error(transparent)]
     UnexpectedError(#[from] anyhow::Error),
 }
 
+/// Registry release an extension is installed from
+pub struct Release {
+    /// Location of the registry index
+    pub registry: String,
+
+    /// Version listed in the registry index
+    pub version: String,
+}
+
 #[async_trait]
 pub trait AddExtension: Sync + Send {
     async fn add(
@@ -61,9 +228,29 @@ pub trait AddExtension: Sync + Send {
         p: &str,
         checksum: Option<&str>,
         force: bool,
+        permissions: Permissions,
+        release: Option<Release>,
     ) -> Result<(), AddExtensionError>;
 }
 
+/// Asks the user to allow the commands requested by an extension
+pub trait ConfirmCommands: Sync + Send {
+    fn confirm(&self, name: &str, commands: &[String]) -> Result<bool, Error>;
+}
+
+/// Confirms requested commands by prompting on the terminal.
+/// Nothing is allowed when the terminal is not interactive.
+pub struct CommandsPrompt(pub Terminal);
+
+impl ConfirmCommands for CommandsPrompt {
+    fn confirm(&self, name: &str, commands: &[String]) -> Result<bool, Error> {
+        eprintln!("Extension {name} requests permission to execute the following commands:");
+        commands.iter().for_each(|c| eprintln!("  - {c}"));
+
+        Ok(self.0.confirm("Allow?", Some(false))?)
+    }
+}
+
 pub struct ExtensionAdder {
     ngn: Engine,
 
@@ -76,6 +263,9 @@ pub struct ExtensionAdder {
 
     // Library interface detector
     detector: Arc<dyn DetectIfaces>,
+
+    // Requested commands confirmation
+    confirm: Arc<dyn ConfirmCommands>,
 }
 
 impl ExtensionAdder {
@@ -85,6 +275,7 @@ impl ExtensionAdder {
         extensions_dir: PathBuf,
         precompiles_dir: PathBuf,
         detector: Arc<dyn DetectIfaces>,
+        confirm: Arc<dyn ConfirmCommands>,
     ) -> Self {
         Self {
             ngn,
@@ -92,106 +283,127 @@ impl ExtensionAdder {
             extensions_dir,
             precompiles_dir,
             detector,
+            confirm,
         }
     }
 }
 
 #[async_trait]
-impl AddExtension for ExtensionAdder {
-    async fn add(
+pub trait StageExtension: Sync + Send {
+    /// Stages an extension: writes its component and pre-compiled component next to their final
+    /// paths, detects its interfaces and retrieves its spec, asking to allow the commands it
+    /// requests that are not allowed yet. The staged files are removed if the extension is rejected.
+    ///
+    /// The staged extension has to be installed with [`commit`] or discarded with [`discard`].
+    async fn stage(
         &self,
         name: &str,
-        p: &str,
-        checksum: Option<&str>,
-        force: bool,
-    ) -> Result<(), AddExtensionError> {
-        let mut m = self.mh.load().context("failed to load manifest")?;
-
-        if let Some(existing) = m.xs.iter().position(|x| x.name == name) {
-            if !force {
-                return Err(AddExtensionError::AlreadyExists(name.to_owned()));
-            }
-            // Remove existing extension files
-            let old_ext = &m.xs[existing];
-            for p in [&old_ext.pre, &old_ext.wasm] {
-                remove_file(p).context("failed to remove existing extension files")?;
-            }
-            m.xs.remove(existing);
-        }
-
-        let ext = match AdditionType::try_from(p)? {
-            AdditionType::File(path) => {
-                read(&path).context(format!("failed to read extension file: {:?}", path))?
-            }
-
-            AdditionType::Uri(uri) => get(uri.to_string())
-                .await
-                .context("failed to download file")?
-                .bytes()
-                .await
-                .context("failed to read body")?
-                .to_vec(),
-        };
+        ext: &[u8],
+        permissions: Permissions,
+    ) -> Result<Extension, AddExtensionError>;
+}
 
-        // Calculate and validate checksum if provided
-        let calculated = format!("{:x}", Sha256::digest(&ext));
-        if let Some(expected) = checksum {
-            if expected != calculated {
-                return Err(AddExtensionError::UnexpectedError(anyhow::anyhow!(
-                    "Checksum validation failed\nExpected: {}\nActual: {}",
-                    expected,
-                    calculated
-                )));
-            }
-        }
+#[async_trait]
+impl StageExtension for ExtensionAdder {
+    async fn stage(
+        &self,
+        name: &str,
+        ext: &[u8],
+        permissions: Permissions,
+    ) -> Result<Extension, AddExtensionError> {
+        // Metadata (completed from the spec once it is known)
+        let md = Metadata::read(ext).context("failed to read extension metadata")?;
 
         // Precompile
         let pre = self
             .ngn
-            .precompile_component(&ext)
+            .precompile_component(ext)
             .context("failed to precompile component")?;
 
-        // Compatibility hash
-        // let h = self.ngn.precompile_compatibility_hash();
+        let ext_path = self.extensions_dir.join(format!("{name}.component.wasm"));
+        let pre_path = self.precompiles_dir.join(format!("{name}.precompile.bin"));
 
         // Store extension
-        let ext_path = self.extensions_dir.join(format!("{name}.component.wasm"));
         create_dir_all(&self.extensions_dir).context("failed to create extensions directory")?;
-        write(&ext_path, &ext).context("failed to write extension to disk")?;
+        write(staged(&ext_path), ext).context("failed to write extension to disk")?;
 
         // Store precompile
-        let pre_path = self.precompiles_dir.join(format!("{name}.precompile.bin"));
         create_dir_all(&self.precompiles_dir).context("failed to create precompiles directory")?;
-        write(&pre_path, &pre).context("failed to write precompile to disk")?;
+        write(staged(&pre_path), &pre).context("failed to write precompile to disk")?;
+
+        let mut x = Extension {
+            name: name.to_string(),
+            wasm: ext_path,
+            pre: pre_path,
+            imports: vec![],
+            exports: vec![],
+            checksum: Some(format!("{:x}", Sha256::digest(ext))),
+            compatibility_hash: Some(compatibility_hash(&self.ngn)),
+            permissions,
+            limits: Limits::default(),
+            wasi: false,
+            metadata: md,
+            spec: None,
+            source: None,
+            registry: None,
+            installed_at: Some(humantime::format_rfc3339_seconds(SystemTime::now()).to_string()),
+        };
+
+        if let Err(err) = self.inspect(&mut x, &pre).await {
+            // Clean up temporary files since we're not adding the extension
+            discard(&x)?;
+
+            return Err(err);
+        }
+
+        Ok(x)
+    }
+}
 
+impl ExtensionAdder {
+    /// Fills in the interfaces, spec and metadata of a staged extension, and the commands it may execute
+    async fn inspect(&self, x: &mut Extension, pre: &[u8]) -> Result<(), AddExtensionError> {
         let cmpnt = unsafe {
-            Component::deserialize(&self.ngn, &pre)
+            Component::deserialize(&self.ngn, pre)
                 .context("failed to deserialize precompiled component")?
         };
 
-        let ComponentInterfaces { imports, exports } = self
+        let ComponentInterfaces {
+            imports,
+            host_imports,
+            exports,
+        } = self
             .detector
             .detect(&self.ngn, &cmpnt)
             .await
             .context("failed to detect library interfaces")?;
 
-        let [imports, exports] = [imports, exports].map(|ifaces| {
-            ifaces
-                .into_iter()
-                // Filter out host-provided interfaces (e.g., "icp:cli/...").
-                // These are linked directly by the host application (main.rs)
-                // and should not be part of the inter-extension dependency graph
-                // or dynamic linking process managed by DynamicLinker.
-                .filter(|x| {
-                    // Extract base name without version
-                    let (base_name, _) = parse_interface_name(&x.name);
-                    !base_name.starts_with(HOST_INTERFACE_PREFIX)
-                })
-                .collect::<Vec<_>>()
-        });
+        // Extensions built for another version of the host interfaces cannot be linked
+        let names = host_imports.iter().chain(&exports).map(|x| x.name.as_str());
+
+        if let Some(version) = incompatible_host_version(names) {
+            return Err(AddExtensionError::IncompatibleHost {
+                name: x.name.to_owned(),
+                version,
+            });
+        }
+
+        // Filter out host-provided interfaces (e.g., "icp:cli/cli").
+        // These are used directly by the host application (main.rs)
+        // and should not be part of the inter-extension dependency graph
+        // or dynamic linking process managed by DynamicLinker.
+        let exports = exports
+            .into_iter()
+            .filter(|x| !is_host_interface(&x.name))
+            .collect::<Vec<_>>();
+
+        // WASI is linked for extensions that import any of its interfaces
+        x.wasi = host_imports
+            .iter()
+            .any(|x| x.name.starts_with(WASI_INTERFACE_PREFIX));
 
         // Create a new extension with detected library interfaces
-        let imports = imports
+        x.imports = imports
             .into_iter()
             .map(|imp| Interface {
                 name: imp.name,
@@ -199,7 +411,7 @@ impl AddExtension for ExtensionAdder {
             })
             .collect();
 
-        let exports = exports
+        x.exports = exports
             .into_iter()
             .map(|exp| Interface {
                 name: exp.name,
@@ -207,42 +419,551 @@ impl AddExtension for ExtensionAdder {
             })
             .collect();
 
-        let x = Extension {
-            name: name.to_string(),
-            wasm: ext_path.clone(),
-            pre: pre_path.clone(),
-            imports,
-            exports,
-            checksum: checksum.map(|s| s.to_string()).or(Some(calculated)),
+        // Command specification
+        let spec = fetch_spec(&self.ngn, &cmpnt)
+            .await
+            .context("failed to retrieve extension spec")?;
+
+        let spec = CommandSpec::parse(&spec).map_err(AddExtensionError::InvalidSpec)?;
+
+        // Registration interfaces must be imported from a provider
+        if let Some(interface) = spec.registers.iter().find(|r| {
+            !x.imports
+                .iter()
+                .any(|imp| parse_interface_name(&imp.name).0 == **r)
+        }) {
+            return Err(AddExtensionError::UnknownRegistration {
+                name: x.name.to_owned(),
+                interface: interface.to_owned(),
+            });
+        }
+
+        // Commands (requested commands must be allowed explicitly)
+        let requested: Vec<String> = spec
+            .commands
+            .iter()
+            .filter(|c| !x.permissions.commands.contains(c))
+            .cloned()
+            .collect();
+
+        if !requested.is_empty() {
+            if !self.confirm.confirm(&x.name, &requested)? {
+                return Err(AddExtensionError::CommandsNotAllowed {
+                    name: x.name.to_owned(),
+                    commands: requested,
+                });
+            }
+
+            x.permissions.commands.extend(requested);
+        }
+
+        x.metadata = x.metadata.clone().or_spec(&spec);
+        x.spec = Some(spec);
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AddExtension for ExtensionAdder {
+    async fn add(
+        &self,
+        name: &str,
+        p: &str,
+        checksum: Option<&str>,
+        force: bool,
+        permissions: Permissions,
+        release: Option<Release>,
+    ) -> Result<(), AddExtensionError> {
+        let m = self.mh.load().context("failed to load manifest")?;
+
+        // The existing extension is only replaced once the new one is ready
+        if !force && m.xs.iter().any(|x| x.name == name) {
+            return Err(AddExtensionError::AlreadyExists(name.to_owned()));
+        }
+
+        let (ext, source) = read_component(p).await?;
+
+        // Calculate and validate checksum if provided
+        let calculated = format!("{:x}", Sha256::digest(&ext));
+        if let Some(expected) = checksum {
+            if expected != calculated {
+                return Err(AddExtensionError::UnexpectedError(anyhow::anyhow!(
+                    "Checksum validation failed\nExpected: {}\nActual: {}",
+                    expected,
+                    calculated
+                )));
+            }
+        }
+
+        // A replaced extension keeps its limits, and the permissions that are not granted anew
+        let previous = m.xs.iter().find(|x| x.name == name);
+
+        let permissions = match previous {
+            Some(y) => permissions.or(&y.permissions),
+            None => permissions,
+        };
+
+        let mut x = self.stage(name, &ext, permissions).await?;
+
+        x.limits = previous.map(|y| y.limits.clone()).unwrap_or_default();
+        x.source = Some(source);
+
+        // The version listed in the registry takes precedence
+        if let Some(r) = release {
+            x.metadata.version = Some(r.version);
+            x.registry = Some(r.registry);
+        }
+
+        // Validate dependencies (against the other extensions)
+        let others = Manifest {
+            xs: m.xs.iter().filter(|y| y.name != name).cloned().collect(),
         };
 
-        // Validate dependencies
-        if let Err(err) = DependencyGraph::new(&m)
+        if let Err(err) = DependencyGraph::new(&others)
             .context("failed to create dependency graph")?
-            .validate_extension_dependencies(&x, &m)
+            .validate_extension_dependencies(&x, &others)
         {
             // Clean up temporary files since we're not adding the extension
-            for p in [&ext_path, &pre_path] {
-                if p.exists() {
-                    remove_file(p).context("failed to remove temporary file")?;
-                }
-            }
+            discard(&x)?;
 
             return Err(err.into());
         }
 
         // Update manifest
-        let mut m = m;
-        m.xs.push(x);
-
-        self.mh
-            .store(&m)
-            .context("failed to store extensions manifest")?;
+        commit(&self.mh, m, x)?;
 
         Ok(())
     }
 }
 
+/// Reads a component from a local path or downloads it from a URI
+///
+/// # Returns
+///
+/// The component, and where it was read from (made absolute for local paths)
+pub async fn read_component(p: &str) -> Result<(Vec<u8>, String), Error> {
+    Ok(match AdditionType::try_from(p)? {
+        AdditionType::File(path) => (
+            read(&path).context(format!("failed to read extension file: {:?}", path))?,
+            absolute(&path)
+                .context("failed to resolve extension path")?
+                .to_string_lossy()
+                .into_owned(),
+        ),
+
+        AdditionType::Uri(uri) => (
+            get(uri.to_string())
+                .await
+                .and_then(|r| r.error_for_status())
+                .context("failed to download file")?
+                .bytes()
+                .await
+                .context("failed to read body")?
+                .to_vec(),
+            uri.to_string(),
+        ),
+    })
+}
+
+/// Appends a suffix to a path, e.g. `build.component.wasm.new`
+fn suffixed(p: &Path, suffix: &str) -> PathBuf {
+    let mut p = p.as_os_str().to_owned();
+    p.push(".");
+    p.push(suffix);
+    p.into()
+}
+
+/// Path of the staged copy of an extension file, which replaces it when the extension is committed
+fn staged(p: &Path) -> PathBuf {
+    suffixed(p, "new")
+}
+
+/// Path of the backup of an extension file being replaced, kept until the manifest is updated
+fn backup(p: &Path) -> PathBuf {
+    suffixed(p, "old")
+}
+
+/// Removes the staged files of an extension that is not installed after all
+fn discard(x: &Extension) -> Result<(), Error> {
+    for p in [&x.wasm, &x.pre].map(|p| staged(p)) {
+        if p.exists() {
+            remove_file(&p).context("failed to remove temporary file")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Installs a staged extension, replacing the installed extension with the same name, if any.
+///
+/// Replaced files are kept as backups until the manifest is stored and restored if that fails,
+/// so the extension is either fully replaced or left as it was.
+fn commit(mh: &ManifestHandle, mut m: Manifest, x: Extension) -> Result<(), Error> {
+    let old =
+        m.xs.iter()
+            .position(|y| y.name == x.name)
+            .map(|idx| m.xs.remove(idx));
+
+    // Files swapped in so far, and whether they replaced an existing file
+    let mut swapped = vec![];
+
+    if let Err(err) = swap(mh, &mut m, &x, &mut swapped) {
+        for (p, replaced) in swapped.into_iter().rev() {
+            let _ = match replaced {
+                true => rename(backup(&p), &p),
+                false => remove_file(&p),
+            };
+        }
+
+        discard(&x)?;
+
+        return Err(err);
+    }
+
+    // Leftovers are harmless, so failing to remove them is not an error
+    for (p, replaced) in swapped {
+        if replaced {
+            let _ = remove_file(backup(&p));
+        }
+    }
+
+    if let Some(old) = old {
+        for p in [&old.wasm, &old.pre] {
+            if p != &x.wasm && p != &x.pre {
+                let _ = remove_file(p);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn swap(
+    mh: &ManifestHandle,
+    m: &mut Manifest,
+    x: &Extension,
+    swapped: &mut Vec<(PathBuf, bool)>,
+) -> Result<(), Error> {
+    for p in [&x.wasm, &x.pre] {
+        let replaced = p.exists();
+
+        if replaced {
+            rename(p, backup(p)).context("failed to back up extension file")?;
+        }
+
+        swapped.push((p.to_owned(), replaced));
+
+        rename(staged(p), p).context("failed to install extension file")?;
+    }
+
+    m.xs.push(x.clone());
+
+    mh.store(m).context("failed to store extensions manifest")?;
+
+    Ok(())
+}
+
+/// Outcome of updating an extension
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum UpdateOutcome {
+    /// The extension was updated
+    Updated {
+        from: Option<String>,
+        to: Option<String>,
+        changes: Vec<InterfaceChange>,
+    },
+
+    /// No newer version is available
+    UpToDate { version: Option<String> },
+
+    /// A newer version is available, but it is not compatible with the installed one
+    Incompatible {
+        version: Option<String>,
+        available: String,
+    },
+
+    /// The extension was installed without recording where from
+    NoSource,
+}
+
+impl UpdateOutcome {
+    /// Describes the outcome for an extension, followed by the interface changes of an update
+    pub fn summary(&self, name: &str) -> String {
+        let v = |v: &Option<String>| v.clone().unwrap_or("unknown version".to_owned());
+
+        match self {
+            Self::Updated { from, to, changes } => {
+                let mut out = format!("Updated {name} from {} to {}", v(from), v(to));
+
+                for c in changes {
+                    out.push_str(&format!("\n  {c}"));
+                }
+
+                out
+            }
+
+            Self::UpToDate { version } => format!("{name} is up to date ({})", v(version)),
+
+            Self::Incompatible { version, available } => format!(
+                "{name} is up to date ({}), but {available} is available (use --major to update)",
+                v(version)
+            ),
+
+            Self::NoSource => {
+                format!("{name} has no recorded source (install it again to enable updates)")
+            }
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateExtensionError {
+    #[error("not found: {0}")]
+    NotFound(String),
+
+    #[error("extension {0} is no longer listed in its registry")]
+    NotInRegistry(String),
+
+    #[error("checksum mismatch for extension {name}\nExpected: {expected}\nActual: {actual}")]
+    ChecksumMismatch {
+        name: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error(
+        "updating extension {name} would break extensions depending on it:\n{}",
+        changes.iter().map(|c| format!("  {c}")).collect::<Vec<_>>().join("\n")
+    )]
+    BreaksDependents {
+        name: String,
+        changes: Vec<InterfaceChange>,
+    },
+
+    #[error(transparent)]
+    FetchIndex(#[from] FetchIndexError),
+
+    #[error(transparent)]
+    AddExtension(#[from] AddExtensionError),
+
+    #[error(transparent)]
+    DependencyValidationFailed(#[from] DependencyError),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+#[async_trait]
+pub trait UpdateExtension: Sync + Send {
+    /// Updates an extension to the newest version available from its registry, or to the
+    /// component currently found at its source. Unless `major` is set, only versions compatible
+    /// with the installed one (as per semver) are considered.
+    async fn update(&self, name: &str, major: bool) -> Result<UpdateOutcome, UpdateExtensionError>;
+}
+
+pub struct ExtensionUpdater {
+    mh: ManifestHandle,
+
+    // Extension stager (used to stage the new version)
+    adder: Arc<dyn StageExtension>,
+
+    // Registry index fetcher
+    fetcher: Arc<dyn FetchIndex>,
+}
+
+impl ExtensionUpdater {
+    pub fn new(
+        mh: ManifestHandle,
+        adder: Arc<dyn StageExtension>,
+        fetcher: Arc<dyn FetchIndex>,
+    ) -> Self {
+        Self { mh, adder, fetcher }
+    }
+}
+
+#[async_trait]
+impl UpdateExtension for ExtensionUpdater {
+    async fn update(&self, name: &str, major: bool) -> Result<UpdateOutcome, UpdateExtensionError> {
+        let m = self.mh.load().context("failed to load manifest")?;
+
+        let x =
+            m.xs.iter()
+                .find(|x| x.name == name)
+                .cloned()
+                .ok_or(UpdateExtensionError::NotFound(name.to_owned()))?;
+
+        let version = x.metadata.version.clone();
+        let installed = version.as_deref().and_then(|v| Version::parse(v).ok());
+
+        // Versions compatible with the installed one
+        let compatible = |v: &Version| match &installed {
+            Some(installed) => major || compatible_with(installed).matches(v),
+            None => true,
+        };
+
+        let newer = |v: &Version| installed.as_ref().is_none_or(|installed| v > installed);
+
+        let (ext, source, release) = match (&x.registry, &x.source) {
+            (Some(registry), _) => {
+                let idx = self.fetcher.fetch(registry).await?;
+
+                // Versions built for another host interface are not considered
+                let entry = idx
+                    .get(name)
+                    .map(supported)
+                    .ok_or(UpdateExtensionError::NotInRegistry(name.to_owned()))?;
+
+                let candidate = match (&installed, major) {
+                    (Some(installed), false) => entry.resolve(Some(&compatible_with(installed))),
+                    _ => entry.latest(),
+                };
+
+                let Some(c) = candidate.filter(|c| newer(&c.version)) else {
+                    return Ok(match entry.latest().filter(|l| newer(&l.version)) {
+                        Some(l) => UpdateOutcome::Incompatible {
+                            version,
+                            available: l.version.to_string(),
+                        },
+                        None => UpdateOutcome::UpToDate { version },
+                    });
+                };
+
+                if x.checksum.as_ref() == Some(&c.sha256) {
+                    return Ok(UpdateOutcome::UpToDate { version });
+                }
+
+                // Components listed in a local index are read from disk
+                let url = resolve_url(registry, &c.url);
+                let p = match local_path(&url)? {
+                    Some(p) => p.to_string_lossy().into_owned(),
+                    None => url,
+                };
+
+                let (ext, source) = read_component(&p).await?;
+
+                let actual = format!("{:x}", Sha256::digest(&ext));
+                if actual != c.sha256 {
+                    return Err(UpdateExtensionError::ChecksumMismatch {
+                        name: name.to_owned(),
+                        expected: c.sha256.to_owned(),
+                        actual,
+                    });
+                }
+
+                let release = Release {
+                    registry: registry.to_owned(),
+                    version: c.version.to_string(),
+                };
+
+                (ext, source, Some(release))
+            }
+
+            (None, Some(source)) => {
+                let (ext, source) = read_component(source).await?;
+
+                if x.checksum.as_ref() == Some(&format!("{:x}", Sha256::digest(&ext))) {
+                    return Ok(UpdateOutcome::UpToDate { version });
+                }
+
+                // Components reporting no version (or the same one) are assumed to be rebuilds
+                let md = Metadata::read(&ext).context("failed to read extension metadata")?;
+
+                if let Some(v) = md.version.as_deref().and_then(|v| Version::parse(v).ok()) {
+                    if installed.as_ref().is_some_and(|installed| &v < installed) {
+                        return Ok(UpdateOutcome::UpToDate { version });
+                    }
+
+                    if !compatible(&v) {
+                        return Ok(UpdateOutcome::Incompatible {
+                            version,
+                            available: v.to_string(),
+                        });
+                    }
+                }
+
+                (ext, source, None)
+            }
+
+            (None, None) => return Ok(UpdateOutcome::NoSource),
+        };
+
+        // Stage the new version, re-detecting its interfaces
+        let mut y = self.adder.stage(name, &ext, x.permissions.clone()).await?;
+
+        y.limits = x.limits.clone();
+        y.source = Some(source);
+        y.registry = x.registry.clone();
+
+        if let Some(r) = release {
+            y.metadata.version = Some(r.version);
+        }
+
+        let changes = export_changes(&x, &y, &m);
+
+        if let Err(err) = validate_update(&m, &y, &changes) {
+            // Clean up temporary files since we're not updating the extension
+            discard(&y)?;
+
+            return Err(err);
+        }
+
+        let to = y.metadata.version.clone();
+
+        commit(&self.mh, m, y)?;
+
+        Ok(UpdateOutcome::Updated {
+            from: version,
+            to,
+            changes,
+        })
+    }
+}
+
+/// Requirement matching the versions compatible with a version (as per semver, e.g. `^1.2.0`)
+fn compatible_with(v: &Version) -> VersionReq {
+    VersionReq {
+        comparators: vec![Comparator {
+            op: Op::Caret,
+            major: v.major,
+            minor: Some(v.minor),
+            patch: Some(v.patch),
+            pre: v.pre.clone(),
+        }],
+    }
+}
+
+/// Checks that the new version of an extension can replace the installed one: its own
+/// dependencies must be satisfied by the other extensions, and it must keep exporting
+/// what they import from it
+fn validate_update(
+    m: &Manifest,
+    x: &Extension,
+    changes: &[InterfaceChange],
+) -> Result<(), UpdateExtensionError> {
+    let others = Manifest {
+        xs: m.xs.iter().filter(|y| y.name != x.name).cloned().collect(),
+    };
+
+    DependencyGraph::new(&others)?.validate_extension_dependencies(x, &others)?;
+
+    let breaking: Vec<_> = changes
+        .iter()
+        .filter(|c| c.is_breaking())
+        .cloned()
+        .collect();
+
+    if !breaking.is_empty() {
+        return Err(UpdateExtensionError::BreaksDependents {
+            name: x.name.to_owned(),
+            changes: breaking,
+        });
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum RemoveExtensionError {
     #[error("not found: {0}")]
@@ -309,7 +1030,7 @@ pub enum ListExtensionsError {
 
 #[async_trait]
 pub trait ListExtensions: Sync + Send {
-    async fn list(&self) -> Result<Vec<String>, ListExtensionsError>;
+    async fn list(&self) -> Result<Vec<Extension>, ListExtensionsError>;
 }
 
 pub struct ExtensionLister {
@@ -324,16 +1045,363 @@ impl ExtensionLister {
 
 #[async_trait]
 impl ListExtensions for ExtensionLister {
-    async fn list(&self) -> Result<Vec<String>, ListExtensionsError> {
+    async fn list(&self) -> Result<Vec<Extension>, ListExtensionsError> {
         let m = self
             .mh
             .load()
             .context("failed to load extensions manifest")?;
 
-        Ok(m.xs
-            .iter()
-            .cloned()
-            .map(|x| x.name)
-            .collect::<Vec<String>>())
+        Ok(m.xs)
+    }
+}
+
+/// Describes an installed extension, with the sizes of its component and pre-compiled component.
+///
+/// JSON output includes every detail, with exact checksums and sizes in bytes, while text output
+/// is meant for a table: it leaves out the description, authors and repository, shortens the
+/// checksum and shows sizes in human-readable units.
+pub fn describe(x: &Extension, format: OutputFormat) -> Value {
+    let size = |p: &Path| metadata(p).map(|md| md.len()).ok();
+
+    let (size, precompiled) = (size(&x.wasm), size(&x.pre));
+
+    match format {
+        OutputFormat::Json => json!({
+            "name": x.name,
+            "version": x.metadata.version,
+            "description": x.metadata.description,
+            "authors": x.metadata.authors,
+            "repository": x.metadata.repository,
+            "source": x.source,
+            "registry": x.registry,
+            "installed_at": x.installed_at,
+            "checksum": x.checksum,
+            "size": size,
+            "precompiled_size": precompiled,
+        }),
+
+        OutputFormat::Text => json!({
+            "name": x.name,
+            "version": x.metadata.version,
+            "source": x.source,
+            "installed": x.installed_at,
+            "checksum": x.checksum.as_ref().map(|c| c.chars().take(12).collect::<String>()),
+            "size": size.map(human_size),
+            "precompiled": precompiled.map(human_size),
+        }),
+    }
+}
+
+/// Formats a size in bytes with a binary unit, e.g. `1.9 MiB`
+fn human_size(n: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if n < 1024 {
+        return format!("{n} B");
+    }
+
+    let mut v = n as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for u in &UNITS[1..] {
+        if v < 1024.0 {
+            break;
+        }
+
+        v /= 1024.0;
+        unit = u;
+    }
+
+    format!("{v:.1} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use icp_distribution::{RegistryIndex, RegistryVersion};
+    use tempfile::TempDir;
+
+    use crate::registry::testing::FakeIndex;
+
+    use super::*;
+
+    /// Stages components as they are, with the given imports, optionally leaving out the
+    /// pre-compiled component (so that installing it fails after the component is swapped in)
+    struct FakeStager {
+        dir: PathBuf,
+        imports: Vec<Interface>,
+        skip_precompile: bool,
+    }
+
+    #[async_trait]
+    impl StageExtension for FakeStager {
+        async fn stage(
+            &self,
+            name: &str,
+            ext: &[u8],
+            permissions: Permissions,
+        ) -> Result<Extension, AddExtensionError> {
+            let x = extension(&self.dir, name, ext, permissions);
+
+            write(staged(&x.wasm), ext).unwrap();
+
+            if !self.skip_precompile {
+                write(staged(&x.pre), precompiled(ext)).unwrap();
+            }
+
+            Ok(Extension {
+                imports: self.imports.clone(),
+                ..x
+            })
+        }
+    }
+
+    fn component(v: &str) -> Vec<u8> {
+        format!("component {v}").into_bytes()
+    }
+
+    fn precompiled(ext: &[u8]) -> Vec<u8> {
+        [b"precompiled ", ext].concat()
+    }
+
+    fn extension(dir: &Path, name: &str, ext: &[u8], permissions: Permissions) -> Extension {
+        Extension {
+            name: name.to_owned(),
+            wasm: dir.join(format!("{name}.component.wasm")),
+            pre: dir.join(format!("{name}.precompile.bin")),
+            imports: vec![],
+            exports: vec![],
+            checksum: Some(format!("{:x}", Sha256::digest(ext))),
+            compatibility_hash: None,
+            permissions,
+            limits: Limits::default(),
+            wasi: false,
+            spec: None,
+            metadata: Metadata::default(),
+            source: None,
+            registry: None,
+            installed_at: None,
+        }
+    }
+
+    /// Test environment: `build` 1.2.0 installed from a registry listing 1.2.1, 1.4.1 and 2.0.0
+    struct Env {
+        _tmp: TempDir,
+        dir: PathBuf,
+        mh: ManifestHandle,
+    }
+
+    impl Env {
+        fn new() -> Self {
+            let tmp = TempDir::new().unwrap();
+            let dir = tmp.path().to_owned();
+            let registry = dir.join("registry");
+
+            create_dir_all(&registry).unwrap();
+
+            for v in ["1.2.1", "1.4.1", "2.0.0"] {
+                write(
+                    registry.join(format!("build-{v}.component.wasm")),
+                    component(v),
+                )
+                .unwrap();
+            }
+
+            let mut x = extension(&dir, "build", &component("1.2.0"), Permissions::default());
+            x.metadata.version = Some("1.2.0".to_owned());
+            x.registry = Some(registry.join("index.json").to_string_lossy().into_owned());
+
+            write(&x.wasm, component("1.2.0")).unwrap();
+            write(&x.pre, precompiled(&component("1.2.0"))).unwrap();
+
+            let mh = ManifestHandle(dir.join("manifest.json"));
+            mh.store(&Manifest { xs: vec![x] }).unwrap();
+
+            Self { _tmp: tmp, dir, mh }
+        }
+
+        /// Index listing the published versions, and optionally a version whose component is missing
+        fn index(&self, missing: Option<&str>) -> RegistryIndex {
+            let mut idx = RegistryIndex::default();
+
+            for v in ["1.2.1", "1.4.1", "2.0.0"].into_iter().chain(missing) {
+                idx.publish(
+                    "build",
+                    None,
+                    RegistryVersion {
+                        version: Version::parse(v).unwrap(),
+                        url: format!("build-{v}.component.wasm"),
+                        sha256: format!("{:x}", Sha256::digest(component(v))),
+                        imports: vec![],
+                        exports: vec![],
+                    },
+                );
+            }
+
+            idx
+        }
+
+        fn stager(&self, imports: Vec<Interface>, skip_precompile: bool) -> FakeStager {
+            FakeStager {
+                dir: self.dir.clone(),
+                imports,
+                skip_precompile,
+            }
+        }
+
+        fn updater(&self, idx: RegistryIndex, stager: FakeStager) -> ExtensionUpdater {
+            ExtensionUpdater::new(
+                self.mh.clone(),          // mh
+                Arc::new(stager),         // adder
+                Arc::new(FakeIndex(idx)), // fetcher
+            )
+        }
+
+        fn installed(&self) -> Extension {
+            self.mh.load().unwrap().xs.remove(0)
+        }
+
+        /// Asserts that the installed extension is at the given version, with no leftover files
+        fn assert_installed(&self, v: &str) {
+            let x = self.installed();
+
+            assert_eq!(x.metadata.version.as_deref(), Some(v));
+            assert_eq!(read(&x.wasm).unwrap(), component(v));
+            assert_eq!(read(&x.pre).unwrap(), precompiled(&component(v)));
+
+            for p in [&x.wasm, &x.pre] {
+                assert!(!staged(p).exists());
+                assert!(!backup(p).exists());
+            }
+        }
+    }
+
+    #[test]
+    fn test_compatible_with() {
+        let req = compatible_with(&Version::parse("1.2.0").unwrap());
+
+        assert!(req.matches(&Version::parse("1.4.1").unwrap()));
+        assert!(!req.matches(&Version::parse("2.0.0").unwrap()));
+        assert!(!req.matches(&Version::parse("1.1.0").unwrap()));
+
+        // Minor versions are breaking while the major version is 0
+        let req = compatible_with(&Version::parse("0.2.0").unwrap());
+
+        assert!(req.matches(&Version::parse("0.2.5").unwrap()));
+        assert!(!req.matches(&Version::parse("0.3.0").unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_update_compatible() {
+        let env = Env::new();
+        let update = env.updater(env.index(None), env.stager(vec![], false));
+
+        // The newest compatible version is installed
+        let out = update.update("build", false).await.unwrap();
+
+        assert!(matches!(
+            out,
+            UpdateOutcome::Updated { from, to, .. }
+                if from.as_deref() == Some("1.2.0") && to.as_deref() == Some("1.4.1")
+        ));
+
+        env.assert_installed("1.4.1");
+
+        // Incompatible versions are only installed with `major`
+        let out = update.update("build", false).await.unwrap();
+
+        assert!(matches!(
+            out,
+            UpdateOutcome::Incompatible { available, .. } if available == "2.0.0"
+        ));
+
+        update.update("build", true).await.unwrap();
+        env.assert_installed("2.0.0");
+
+        let out = update.update("build", true).await.unwrap();
+
+        assert!(
+            matches!(out, UpdateOutcome::UpToDate { version } if version.as_deref() == Some("2.0.0"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_failed_download() {
+        let env = Env::new();
+
+        // The newest compatible version is listed, but its component is missing
+        let update = env.updater(env.index(Some("1.5.0")), env.stager(vec![], false));
+
+        assert!(update.update("build", false).await.is_err());
+
+        env.assert_installed("1.2.0");
+    }
+
+    #[tokio::test]
+    async fn test_update_failed_dependencies() {
+        let env = Env::new();
+
+        // The new version imports an interface no other extension provides
+        let imports = vec![Interface {
+            name: "test:missing/lib".to_owned(),
+            funcs: vec!["f".to_owned()],
+        }];
+
+        let update = env.updater(env.index(None), env.stager(imports, false));
+
+        assert!(matches!(
+            update.update("build", false).await,
+            Err(UpdateExtensionError::DependencyValidationFailed(_))
+        ));
+
+        env.assert_installed("1.2.0");
+    }
+
+    #[tokio::test]
+    async fn test_update_failed_swap() {
+        let env = Env::new();
+
+        // The component is swapped in, but installing the pre-compiled component fails
+        let update = env.updater(env.index(None), env.stager(vec![], true));
+
+        assert!(update.update("build", false).await.is_err());
+
+        env.assert_installed("1.2.0");
+    }
+
+    #[tokio::test]
+    async fn test_commit_restores_backup() {
+        let env = Env::new();
+        let m = env.mh.load().unwrap();
+
+        let stager = env.stager(vec![], false);
+
+        let mut y = stager
+            .stage("build", &component("1.4.1"), Permissions::default())
+            .await
+            .unwrap();
+
+        y.metadata.version = Some("1.4.1".to_owned());
+
+        // Storing the manifest fails once both files are swapped in
+        let blocked = env.dir.join("blocked");
+        write(&blocked, "").unwrap();
+
+        let err = commit(&ManifestHandle(blocked.join("manifest.json")), m, y);
+
+        assert!(err.is_err());
+
+        env.assert_installed("1.2.0");
+
+        // The same files are committed once the manifest can be stored
+        let mut y = stager
+            .stage("build", &component("1.4.1"), Permissions::default())
+            .await
+            .unwrap();
+
+        y.metadata.version = Some("1.4.1".to_owned());
+
+        commit(&env.mh, env.mh.load().unwrap(), y).unwrap();
+
+        env.assert_installed("1.4.1");
     }
 }
@@ -0,0 +1,87 @@
+use std::{
+    env::var,
+    io::{stderr, IsTerminal},
+};
+
+use tracing::{enabled, error_span, event, level_filters::LevelFilter, Level};
+use tracing_subscriber::EnvFilter;
+
+use crate::icp::cli::log::Level as ExtensionLevel;
+
+/// Environment variable holding additional filter directives (e.g. `ICP_LOG=debug`)
+const ENV_LOG: &str = "ICP_LOG";
+
+/// Target of the records emitted by the host itself
+const TARGET_HOST: &str = "icp";
+
+/// Target of the records emitted on behalf of extensions
+const TARGET_EXTENSION: &str = "extension";
+
+/// Installs the global subscriber, writing records to stderr.
+///
+/// The verbosity flags adjust the level (`info` by default) of the records emitted by the host
+/// and by extensions, while dependencies only log warnings and errors. Directives from `ICP_LOG`
+/// take precedence over both.
+pub fn init(verbose: u8, quiet: u8) {
+    let level = match 2 + verbose as i16 - quiet as i16 {
+        ..0 => LevelFilter::OFF,
+        0 => LevelFilter::ERROR,
+        1 => LevelFilter::WARN,
+        2 => LevelFilter::INFO,
+        3 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+
+    let directives = [
+        level.min(LevelFilter::WARN).to_string(),
+        format!("{TARGET_HOST}={level}"),
+        format!("{TARGET_EXTENSION}={level}"),
+        var(ENV_LOG).unwrap_or_default(),
+    ];
+
+    let filter = EnvFilter::builder().parse_lossy(directives.join(","));
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(stderr)
+        .with_ansi(stderr().is_terminal())
+        .without_time()
+        .with_target(false)
+        .init();
+}
+
+/// Emits a record on behalf of an extension, within an `extension{name=...}` span (which
+/// allows filtering by extension, e.g. `ICP_LOG=[extension{name=project}]=debug`).
+/// Fields are appended to the message, since their names are only known at runtime.
+pub fn emit(extension: &str, level: ExtensionLevel, message: &str, fields: &[(String, String)]) {
+    let fields: String = fields.iter().map(|(k, v)| format!(" {k}={v}")).collect();
+
+    // The span is enabled at any level, so that records are never emitted without it
+    let _span = error_span!(target: TARGET_EXTENSION, "extension", name = %extension).entered();
+
+    // Levels must be known statically by the `event!` macro
+    macro_rules! emit {
+        ($lvl:expr) => {
+            event!(target: TARGET_EXTENSION, $lvl, "{message}{fields}")
+        };
+    }
+
+    match level {
+        ExtensionLevel::Trace => emit!(Level::TRACE),
+        ExtensionLevel::Debug => emit!(Level::DEBUG),
+        ExtensionLevel::Info => emit!(Level::INFO),
+        ExtensionLevel::Warn => emit!(Level::WARN),
+        ExtensionLevel::Error => emit!(Level::ERROR),
+    }
+}
+
+/// Checks whether records emitted on behalf of extensions at the given level are enabled
+pub fn is_enabled(level: ExtensionLevel) -> bool {
+    match level {
+        ExtensionLevel::Trace => enabled!(target: TARGET_EXTENSION, Level::TRACE),
+        ExtensionLevel::Debug => enabled!(target: TARGET_EXTENSION, Level::DEBUG),
+        ExtensionLevel::Info => enabled!(target: TARGET_EXTENSION, Level::INFO),
+        ExtensionLevel::Warn => enabled!(target: TARGET_EXTENSION, Level::WARN),
+        ExtensionLevel::Error => enabled!(target: TARGET_EXTENSION, Level::ERROR),
+    }
+}
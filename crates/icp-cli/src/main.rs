@@ -1,50 +1,129 @@
 use std::{
-    collections::HashMap,
-    env::args_os,
-    ffi::OsString,
+    collections::{HashMap, HashSet},
+    env::{args_os, current_dir, current_exe, var, vars},
+    ffi::{OsStr, OsString},
     fs::{create_dir_all, read},
-    path::{Path, PathBuf},
+    io::{stdout, ErrorKind},
+    iter::once,
+    path::{absolute, Path, PathBuf},
+    process::ExitCode,
     str::FromStr,
     sync::{Arc, Mutex},
     time::{SystemTime, UNIX_EPOCH},
 };
 
 use anyhow::{anyhow, bail, Context, Error};
-use clap::{value_parser, Arg, ArgAction, Command};
+use clap::{
+    builder::{PossibleValuesParser, TypedValueParser},
+    parser::ValueSource,
+    value_parser, Arg, ArgAction, ArgMatches, Command,
+};
+use clap_complete::{
+    engine::{ArgValueCompleter, CompletionCandidate},
+    env::Shells,
+    CompleteEnv,
+};
 use dashmap::DashMap;
 use once_cell::sync::Lazy;
 
 use wasmtime::{
-    component::{bindgen, Component, Linker, Val as WasmVal},
-    Config, Engine, Store as WasmStore,
+    component::{bindgen, Component, Linker, Resource, ResourceTable, Val as WasmVal},
+    AsContextMut, Config, Engine, ResourceLimiter, Store as WasmStore, StoreContextMut, Trap,
 };
+use wasmtime_wasi::{DirPerms, FilePerms, IoView, WasiCtx, WasiCtxBuilder, WasiView};
 
 use icp_core::{
-    component::{DynamicLinker, FunctionRegistry},
+    component::{CallTracker, DynamicLinker, FunctionRegistry},
     dependency::DependencyGraph,
-    interface::IfaceDetector,
-    manifest::{Load, LoadError, Manifest, ManifestHandle, Store as _},
+    http::{Allowlist, HttpClient, HttpError, Request},
+    interface::{incompatible_host_version, IfaceDetector, HOST_INTERFACE_VERSION},
+    manifest::{Limits, Load, LoadError, Manifest, ManifestHandle, Permissions, Store as _},
+    output::{self, OutputFormat},
+    random::Random,
+    sandbox::{find_workspace_root, Sandbox, SandboxError},
+    spec::CommandSpec,
+    storage::Storage,
     FunctionRegistryError, Interface,
 };
-use icp_distribution::Distribution;
+use icp_distribution::{parse_version_req, Distribution, DEFAULT_INDEX_URL};
 
 mod extension;
 use extension::{
-    AddExtension, ExtensionAdder, ExtensionLister, ExtensionRemover, ListExtensions,
-    RemoveExtension,
+    compatibility_hash, describe, fetch_spec, recompile, AddExtension, CommandsPrompt,
+    ExtensionAdder, ExtensionLister, ExtensionRemover, ExtensionUpdater, ListExtensions,
+    RemoveExtension, UpdateExtension,
 };
 
+mod registry;
+use registry::{ExtensionInstaller, FetchIndex, IndexFetcher, InstallExtension};
+
 mod spec;
-use spec::CommandSpec;
+
+mod docs;
+use docs::Format;
 
 mod component_invoke;
 
+mod logging;
+
+mod tty;
+use tty::{PromptError, Terminal};
+
+pub mod process;
+pub use process::{baseline_vars, find_program, Process};
+
+pub use indicatif::ProgressBar;
+
 // Service configuration
 const SERVICE_NAME: &str = "icp";
 const ARG_SHORT_MANIFEST: char = 'm';
 const ARG_LONG_MANIFEST: &str = "manifest";
 const ARG_LONG_EXTENSIONS: &str = "extensions-dir";
 const ARG_LONG_PRECOMPILES: &str = "precompiles-dir";
+const ARG_LONG_STORAGE: &str = "storage-dir";
+const ARG_SHORT_VERBOSE: char = 'v';
+const ARG_LONG_VERBOSE: &str = "verbose";
+const ARG_SHORT_QUIET: char = 'q';
+const ARG_LONG_QUIET: &str = "quiet";
+const ARG_SHORT_YES: char = 'y';
+const ARG_LONG_YES: &str = "yes";
+const ARG_LONG_OUTPUT: &str = "output";
+
+// Environment variables
+const ENV_TEST_SEED: &str = "ICP_TEST_SEED";
+const ENV_COMPLETE: &str = "ICP_COMPLETE";
+const ENV_REGISTRY: &str = "ICP_REGISTRY";
+
+// Limits
+const MAX_RANDOM_BYTES: u32 = 1024 * 1024;
+
+// Exit codes (reserved by the host, all other codes are passed through from extensions)
+const EXIT_CODE_FAILURE: u8 = 1;
+const EXIT_CODE_EXTENSION_INIT: u8 = 120;
+const EXIT_CODE_EXTENSION_TRAP: u8 = 121;
+
+/// Errors raised by extensions, each mapped to a dedicated exit code
+#[derive(Debug, thiserror::Error)]
+enum ExtensionError {
+    #[error("failed to initialize extension {name}: {reason}")]
+    InitFailed { name: String, reason: String },
+
+    #[error("extension {name} trapped: {reason:?}")]
+    Trapped { name: String, reason: Error },
+
+    #[error("extension {name} was built for icp:cli@{version}, please update it (icp supports icp:cli@{HOST_INTERFACE_VERSION})")]
+    IncompatibleHost { name: String, version: String },
+}
+
+impl ExtensionError {
+    fn exit_code(&self) -> u8 {
+        match self {
+            ExtensionError::InitFailed { .. } => EXIT_CODE_EXTENSION_INIT,
+            ExtensionError::IncompatibleHost { .. } => EXIT_CODE_EXTENSION_INIT,
+            ExtensionError::Trapped { .. } => EXIT_CODE_EXTENSION_TRAP,
+        }
+    }
+}
 
 // Distribution configuration
 static DISTRIBUTION: Lazy<Distribution> = Lazy::new(|| {
@@ -96,296 +175,1064 @@ static DEFAULT_DIR_PRECOMPILES: Lazy<PathBuf> = Lazy::new(|| match *DISTRIBUTION
     Distribution::Apt => PathBuf::from("/var/lib/icp/precompiles"),
 });
 
+static DEFAULT_DIR_STORAGE: Lazy<PathBuf> = Lazy::new(|| match *DISTRIBUTION {
+    Distribution::Standard => dirs::data_dir()
+        .expect("no data dir found")
+        .join(format!("{SERVICE_NAME}/storage-dir")),
+    Distribution::Homebrew => DEFAULT_PATH_MANIFEST.parent().unwrap().join("storage"),
+    Distribution::NuGet => unimplemented!("nuget paths not yet implemented"),
+    Distribution::Apt => PathBuf::from("/var/lib/icp/storage"),
+});
+
 // WIT Bindings
 use icp::cli::{
-    command::{self, CommandOutput},
-    filesystem, misc,
+    command::{self, CommandOutput, Output, SpawnOptions},
+    environment, filesystem, http, log, misc, storage, terminal,
 };
 
 bindgen!({
     path: "../../wit/cli",
     world: "extension",
     async: true,
+    trappable_imports: ["rand", "random-bytes", "[constructor]progress"],
+    with: {
+        "icp:cli/command/process": Process,
+        "icp:cli/terminal/progress": ProgressBar,
+    },
 });
 
-pub(crate) struct State;
+/// Store data, holding the host state of each loaded extension
+#[derive(Default)]
+pub(crate) struct State {
+    /// Host state of each extension, indexed by its position in the loading order
+    hosts: Vec<ExtensionHost>,
 
-impl misc::Host for State {
-    async fn print(&mut self, s: String) {
-        println!("{s}");
-    }
+    /// Calls into extensions currently in progress, innermost last
+    calls: Vec<Call>,
+}
 
-    async fn time(&mut self) -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("failed to get current time")
-            .as_millis() as u64
+/// A call into an extension that is in progress
+struct Call {
+    /// Index of the extension's host state
+    idx: usize,
+
+    /// Name of the function being called
+    function: String,
+
+    /// Fuel left to the caller when the call was made
+    fuel: u64,
+}
+
+/// A resource limit breached by an extension, which traps the call
+#[derive(Debug, thiserror::Error)]
+#[error("extension {name} exceeded its {resource} limit of {limit} in function {function}")]
+struct LimitExceeded {
+    name: String,
+    function: String,
+    resource: &'static str,
+    limit: String,
+}
+
+impl State {
+    /// Completes a call into an extension, restoring the caller's fuel.
+    /// Running out of fuel is attributed to the innermost call, i.e. the first to complete.
+    fn complete<R>(
+        mut store: StoreContextMut<'_, Self>,
+        out: Result<R, Error>,
+    ) -> Result<R, Error> {
+        let call = store
+            .data_mut()
+            .calls
+            .pop()
+            .context("no call in progress")?;
+
+        store.set_fuel(call.fuel)?;
+
+        out.map_err(|err| {
+            if err.downcast_ref::<Trap>() != Some(&Trap::OutOfFuel)
+                || err.downcast_ref::<LimitExceeded>().is_some()
+            {
+                return err;
+            }
+
+            let host = &store.data().hosts[call.idx];
+
+            err.context(LimitExceeded {
+                name: host.name.clone(),
+                function: call.function,
+                resource: "fuel",
+                limit: host.limits.fuel().to_string(),
+            })
+        })
     }
+}
 
-    async fn rand(&mut self) -> u8 {
-        todo!()
+impl State {
+    /// Host state of the extension being called
+    fn current(&mut self) -> &mut ExtensionHost {
+        let idx = self.calls.last().expect("no call in progress").idx;
+        &mut self.hosts[idx]
     }
 }
 
-/// Host implementation for the `icp:cli/filesystem` interface.
-/// Provides file system access to extensions.
-impl filesystem::Host for State {
-    /// Creates a directory on the host file system.
-    /// Paths are relative to the workspace root where icp-cli runs.
-    async fn create_directory(&mut self, path: String) -> Result<(), String> {
-        std::fs::create_dir_all(&path)
-            .map_err(|err| format!("failed to create directory '{path}': {err}"))?;
+/// WASI is linked against the whole store data, so it acts on behalf of the extension being called
+/// (which is the one importing it, since calls into other extensions are tracked as well)
+impl IoView for State {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.current().table
+    }
+}
 
-        Ok(())
+impl WasiView for State {
+    fn ctx(&mut self) -> &mut WasiCtx {
+        self.current()
+            .wasi
+            .as_mut()
+            .expect("WASI is only linked for extensions with WASI enabled")
     }
+}
 
-    /// Writes content to a file on the host file system.
-    /// Paths are relative to the workspace root where icp-cli runs.
-    async fn write_file(&mut self, path: String, contents: Vec<u8>) -> Result<(), String> {
-        std::fs::write(&path, &contents)
-            .map_err(|err| format!("failed to write file '{path}': {err}"))?;
+impl CallTracker for State {
+    fn enter(
+        mut store: StoreContextMut<'_, Self>,
+        extension: &str,
+        function: &str,
+    ) -> Result<(), Error> {
+        let idx = store
+            .data()
+            .hosts
+            .iter()
+            .position(|h| h.name == extension)
+            .ok_or_else(|| anyhow!("unknown extension {extension}"))?;
+
+        // Each call gets its own fuel budget, so callers are not charged for their callees
+        let fuel = store.get_fuel()?;
+        store.set_fuel(store.data().hosts[idx].limits.fuel())?;
+
+        store.data_mut().calls.push(Call {
+            idx,
+            function: function.to_owned(),
+            fuel,
+        });
 
         Ok(())
     }
 
-    /// Reads content from a file on the host file system.
-    /// Paths are relative to the workspace root where icp-cli runs.
-    async fn read_file(&mut self, path: String) -> Result<Vec<u8>, String> {
-        let out =
-            std::fs::read(&path).map_err(|err| format!("failed to read file '{path}': {err}"))?;
-
-        Ok(out)
+    fn exit(store: StoreContextMut<'_, Self>, out: Result<(), Error>) -> Result<(), Error> {
+        Self::complete(store, out)
     }
 }
 
-/// Host implementation for the `icp:cli/command` interface.
-/// Allows extensions to execute commands on the host system.
-impl command::Host for State {
-    /// Executes a command with the given arguments on the host.
-    /// Returns the standard output, standard error, and exit code.
-    /// If the process is terminated by a signal and has no exit code,
-    /// a default exit code of 1 is returned.
-    async fn execute(
+impl ResourceLimiter for State {
+    fn memory_growing(
         &mut self,
-        command: String,
-        args: Vec<String>,
-    ) -> Result<CommandOutput, String> {
-        use std::process::Command;
-
-        let out = Command::new(command)
-            .args(args)
-            .output()
-            .map_err(|err| format!("failed to execute command: {err}"))?;
+        _current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> Result<bool, Error> {
+        // Memory is only grown by the extension being called
+        let Some(call) = self.calls.last() else {
+            return Ok(true);
+        };
+
+        let host = &self.hosts[call.idx];
+        let limit = host.limits.memory();
+
+        if desired as u64 > limit {
+            return Err(LimitExceeded {
+                name: host.name.clone(),
+                function: call.function.clone(),
+                resource: "memory",
+                limit: format!("{limit} bytes"),
+            }
+            .into());
+        }
 
-        Ok(CommandOutput {
-            stdout: out.stdout,
-            stderr: out.stderr,
-            exit_code: out.status.code().unwrap_or(1) as u32,
-        })
+        Ok(true)
     }
-}
 
-// Directory setup helper
-fn _ensure_directories(
-    manifest: &Path,
-    extensions: &Path,
-    precompiles: &Path,
-) -> Result<(), Error> {
-    if let Some(parent) = manifest.parent() {
-        create_dir_all(parent).context("failed to create manifest directory")?;
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        _desired: usize,
+        _maximum: Option<usize>,
+    ) -> Result<bool, Error> {
+        Ok(true)
     }
-    create_dir_all(extensions).context("failed to create extensions directory")?;
-    create_dir_all(precompiles).context("failed to create precompiles directory")?;
-    Ok(())
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Error> {
-    // Command
-    let c = Command::new(SERVICE_NAME);
+/// Host state of a single extension, used to implement the host interfaces on its behalf
+pub(crate) struct ExtensionHost {
+    /// Name of the extension
+    name: String,
 
-    // Version
-    let c = c.version("1.0.0");
+    /// Filesystem sandbox the extension is confined to
+    sandbox: Sandbox,
 
-    // Arg (manifest)
-    let c = c.arg(
-        Arg::new("manifest")
-            .short(ARG_SHORT_MANIFEST)
-            .long(ARG_LONG_MANIFEST)
-            .default_value(DEFAULT_PATH_MANIFEST.as_os_str())
-            .value_parser(value_parser!(PathBuf)),
-    );
+    /// Permissions granted to the extension (commands, environment variables)
+    permissions: Permissions,
 
-    // Load Manifest
-    let args = args_os().collect::<Vec<_>>();
+    /// Resources (e.g. processes) owned by the extension
+    table: ResourceTable,
 
-    let mpath = args.windows(2).find(|&p| {
-        [
-            format!("-{ARG_SHORT_MANIFEST}"),
-            format!("--{ARG_LONG_MANIFEST}"),
-        ]
-        .iter()
-        .any(|f| *f.as_str() == p[0])
-    });
+    /// Source of random bytes
+    random: Random,
 
-    let mut itr = vec![
-        OsString::from_str(c.get_name())?, // bin
-    ];
+    /// Resource limits applied to the extension
+    limits: Limits,
 
-    if let Some(mpath) = mpath {
-        itr.append(&mut mpath.to_vec());
-    }
+    /// Persistent key-value storage namespace of the extension
+    storage: Storage,
 
-    let ms = c.clone().get_matches_from(itr);
+    /// HTTP client, confined to the hosts the extension is allowed to reach
+    http: HttpClient,
 
-    let mpath = ms
-        .get_one::<PathBuf>("manifest")
-        .context("missing manifest path")?;
+    /// WASI context, for extensions with WASI enabled
+    wasi: Option<WasiCtx>,
 
-    // Manifest (handle)
-    let mh = ManifestHandle(mpath.to_owned());
+    /// Terminal used to prompt the user
+    terminal: Terminal,
 
-    // Setup
-    let c = c
-        .disable_help_subcommand(true)
-        .disable_version_flag(true)
-        .arg_required_else_help(true);
+    /// Format in which results are written
+    output: OutputFormat,
+}
 
-    // Arg (extensions-dir)
-    let c = c.arg(
-        Arg::new("extensions-dir")
-            .long(ARG_LONG_EXTENSIONS)
-            .default_value(DEFAULT_DIR_EXTENSIONS.as_os_str())
-            .value_parser(value_parser!(PathBuf)),
-    );
+impl ExtensionHost {
+    /// Checks that the extension is allowed to execute the given command
+    fn check_command(&self, command: &str) -> Result<(), String> {
+        if !self.permissions.commands.iter().any(|c| c == command) {
+            return Err(format!(
+                "extension {} is not allowed to execute '{command}'",
+                self.name
+            ));
+        }
 
-    // Arg (precompiles-dir)
-    let c = c.arg(
-        Arg::new("precompiles-dir")
-            .long(ARG_LONG_PRECOMPILES)
-            .default_value(DEFAULT_DIR_PRECOMPILES.as_os_str())
-            .value_parser(value_parser!(PathBuf)),
-    );
+        Ok(())
+    }
 
-    // Extension
-    let c = c.subcommand(
-        Command::new("extension")
-            .about("manage extensions")
-            .subcommand_required(true)
-            .subcommand(Command::new("ls").alias("list"))
-            .subcommand(
-                Command::new("add")
-                    .arg(Arg::new("name").long("name").required(true))
-                    .arg(Arg::new("uri").help("Local path or Uri").required(true))
-                    .arg(
-                        Arg::new("checksum")
-                            .long("checksum")
-                            .value_name("SHA256")
-                            .help("Expected SHA256 checksum for verification"),
-                    )
-                    .arg(
-                        Arg::new("force")
-                            .long("force")
-                            .action(ArgAction::SetTrue)
-                            .help("Overwrite existing extension"),
-                    ),
-            )
-            .subcommand(
-                Command::new("rm")
-                    .alias("remove")
-                    .arg(Arg::new("keep").short('k').action(ArgAction::SetTrue))
-                    .arg(Arg::new("name").required(true)),
-            )
-            .subcommand(
-                Command::new("deps")
-                    .about("Show extension dependencies")
-                    .arg(Arg::new("name").help("Extension name").required(false))
-                    .arg(
-                        Arg::new("validate")
-                            .long("validate")
-                            .action(ArgAction::SetTrue)
-                            .help("Validate dependencies"),
-                    ),
-            ),
-    );
+    /// Returns the host's environment variables the extension is allowed to read
+    fn allowed_vars(&self) -> Vec<(String, String)> {
+        vars()
+            .filter(|(k, _)| self.permissions.allows_variable(k))
+            .collect()
+    }
 
-    // Manifest (load)
-    let m = mh.load().or_else(|err| match err {
-        LoadError::NotFound(_) => {
-            let m = Manifest::default();
+    /// Returns the host's environment variables passed to commands run for the extension:
+    /// the baseline ones, and those the extension is allowed to read
+    fn command_vars(&self) -> Vec<(String, String)> {
+        let mut out = baseline_vars(|k| var(k).ok());
+        out.extend(self.allowed_vars());
+        out
+    }
 
-            // TODO(or.ricon): Prompt the user to create the manifest if it doesn't exist
-            mh.store(&m)
-                .context("failed to store initial extensions manifest")?;
+    /// Resolves a path requested by the extension within its filesystem sandbox
+    fn resolve(&self, path: &str) -> Result<PathBuf, filesystem::Error> {
+        self.sandbox
+            .resolve(path)
+            .map_err(|err| self.sandbox_error(err))
+    }
 
-            Ok(m)
+    /// Resolves a path requested by the extension within its filesystem sandbox, without
+    /// following a symlink at its final component
+    fn resolve_entry(&self, path: &str) -> Result<PathBuf, filesystem::Error> {
+        self.sandbox
+            .resolve_entry(path)
+            .map_err(|err| self.sandbox_error(err))
+    }
+
+    /// Converts a sandbox error into a filesystem interface error
+    fn sandbox_error(&self, err: SandboxError) -> filesystem::Error {
+        match err {
+            SandboxError::AccessDenied(p) => filesystem::Error::AccessDenied(format!(
+                "extension {} is not allowed to access '{}'",
+                self.name,
+                p.display()
+            )),
+
+            err => filesystem::Error::Io(err.to_string()),
         }
+    }
+}
 
-        //
-        _ => Err(err),
-    })?;
+/// Directories preopened for a WASI extension, with the guest paths they are preopened at: the
+/// workspace root and the granted directories (those that exist, like in the sandbox) at their own
+/// paths, and the current directory as `.`, so that relative paths resolve like in the sandbox.
+fn preopens(root: &Path, cwd: &Path, permissions: &Permissions) -> Vec<(PathBuf, String)> {
+    once(root)
+        .chain(
+            permissions
+                .directories
+                .iter()
+                .filter(|dir| dir.is_dir())
+                .map(PathBuf::as_path),
+        )
+        .map(|dir| (dir.to_path_buf(), dir.to_string_lossy().into_owned()))
+        .chain(once((cwd.to_path_buf(), ".".to_owned())))
+        .collect()
+}
 
-    // Create dependency graph and resolve loading order
-    let dependency_graph = DependencyGraph::new(&m).context("failed to create dependency graph")?;
+/// Builds the WASI context of an extension, with the directories given by [`preopens`]. Only
+/// the environment variables allowed for the extension are set, the only argument is the
+/// extension's name, stdin is empty and network access is denied. With JSON output, the extension's stdout is
+/// redirected to stderr, like `misc.print`, so that stdout only carries results.
+fn wasi_ctx(
+    name: &str,
+    root: &Path,
+    cwd: &Path,
+    permissions: &Permissions,
+    output: OutputFormat,
+) -> Result<WasiCtx, Error> {
+    let mut b = WasiCtxBuilder::new();
+
+    let vars: Vec<_> = vars()
+        .filter(|(k, _)| permissions.allows_variable(k))
+        .collect();
+
+    match output {
+        OutputFormat::Text => b.inherit_stdout(),
+
+        // Keep stdout for results
+        OutputFormat::Json => b.stdout(wasmtime_wasi::stderr()),
+    };
+
+    b.inherit_stderr()
+        .args(&[name])
+        .envs(&vars)
+        .allow_tcp(false)
+        .allow_udp(false)
+        .allow_ip_name_lookup(false);
+
+    for (dir, guest) in preopens(root, cwd, permissions) {
+        b.preopened_dir(&dir, guest, DirPerms::all(), FilePerms::all())
+            .with_context(|| format!("failed to preopen directory {}", dir.display()))?;
+    }
 
-    // Check for circular dependencies
-    if dependency_graph.has_cycles() {
-        eprintln!("Warning: Circular dependencies detected in extensions:");
-        eprintln!("{}", dependency_graph.format_cycles());
-        eprintln!("Some extensions may not function correctly.");
+    Ok(b.build())
+}
+
+/// Converts an I/O error into a filesystem interface error
+fn fs_error(err: std::io::Error, msg: String) -> filesystem::Error {
+    match err.kind() {
+        ErrorKind::NotFound => filesystem::Error::NotFound(format!("{msg}: {err}")),
+        _ => filesystem::Error::Io(format!("{msg}: {err}")),
     }
+}
 
-    // Validate dependencies
-    if let Err(err) = dependency_graph.validate_dependencies(&m) {
-        eprintln!("Warning: Dependency validation failed: {}", err);
-        eprintln!("Some extensions may not function correctly.");
+impl misc::Host for ExtensionHost {
+    async fn print(&mut self, s: String) {
+        match self.output {
+            OutputFormat::Text => println!("{s}"),
+
+            // Keep stdout for results
+            OutputFormat::Json => eprintln!("{s}"),
+        }
     }
 
-    // Resolve loading order
-    let loading_order = dependency_graph
-        .resolve_loading_order()
-        .context("failed to resolve extension loading order")?;
+    async fn output(&mut self) -> misc::OutputFormat {
+        match self.output {
+            OutputFormat::Text => misc::OutputFormat::Text,
+            OutputFormat::Json => misc::OutputFormat::Json,
+        }
+    }
 
-    // WASM Configuration
-    let mut cfg = Config::new();
-    let cfg = cfg.async_support(true);
+    async fn emit_result(&mut self, value: String) -> Result<(), String> {
+        let v = output::parse(&value).map_err(|err| err.to_string())?;
+        let out = output::render(&v, self.output);
 
-    // Engine
-    let ngn = Engine::new(cfg)?;
+        if !out.is_empty() {
+            println!("{out}");
+        }
 
-    // Linker
-    let mut lnk = Linker::new(&ngn);
+        Ok(())
+    }
 
-    // Components (initialize)
-    let cmpnts: DashMap<String, Component> = DashMap::new();
+    async fn time(&mut self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("failed to get current time")
+            .as_millis() as u64
+    }
 
-    // Load components in dependency order
-    for name in &loading_order {
-        if let Some(extension) = m.xs.iter().find(|x| &x.name == name) {
-            let pre = read(&extension.pre)?;
+    async fn rand(&mut self) -> Result<u8, Error> {
+        let mut out = [0];
+        self.random.fill(&mut out)?;
 
-            let component = unsafe {
-                Component::deserialize(
-                    &ngn, // engine
-                    &pre, // bytes
-                )
-            }?;
+        Ok(out[0])
+    }
 
-            cmpnts.insert(name.clone(), component);
+    async fn random_bytes(&mut self, len: u32) -> Result<Vec<u8>, Error> {
+        if len > MAX_RANDOM_BYTES {
+            bail!(
+                "extension {} requested {len} random bytes, the maximum is {MAX_RANDOM_BYTES}",
+                self.name
+            );
         }
+
+        let mut out = vec![0; len as usize];
+        self.random.fill(&mut out)?;
+
+        Ok(out)
     }
+}
 
-    // Create function registry
-    let reg = FunctionRegistry::new();
-    let reg = Arc::new(Mutex::new(reg));
+/// Host implementation for the `icp:cli/filesystem` interface.
+/// Provides file system access to extensions, confined to their sandbox.
+impl filesystem::Host for ExtensionHost {
+    /// Creates a directory on the host file system.
+    /// Paths are relative to the directory where icp-cli runs.
+    async fn create_directory(&mut self, path: String) -> Result<(), filesystem::Error> {
+        let p = self.resolve(&path)?;
 
-    // Create dynamic linker
-    let mut dynlnk = DynamicLinker::new(Arc::clone(&reg));
+        std::fs::create_dir_all(p)
+            .map_err(|err| fs_error(err, format!("failed to create directory '{path}'")))?;
 
-    // Collect unique interfaces
-    let mut ifaces: HashMap<String, Interface> = HashMap::new();
-    for name in &loading_order {
-        if let Some(extension) = m.xs.iter().find(|x| &x.name == name) {
+        Ok(())
+    }
+
+    /// Writes content to a file on the host file system.
+    /// Paths are relative to the directory where icp-cli runs.
+    async fn write_file(
+        &mut self,
+        path: String,
+        contents: Vec<u8>,
+    ) -> Result<(), filesystem::Error> {
+        let p = self.resolve(&path)?;
+
+        std::fs::write(p, &contents)
+            .map_err(|err| fs_error(err, format!("failed to write file '{path}'")))?;
+
+        Ok(())
+    }
+
+    /// Reads content from a file on the host file system.
+    /// Paths are relative to the directory where icp-cli runs.
+    async fn read_file(&mut self, path: String) -> Result<Vec<u8>, filesystem::Error> {
+        let p = self.resolve(&path)?;
+
+        let out = std::fs::read(p)
+            .map_err(|err| fs_error(err, format!("failed to read file '{path}'")))?;
+
+        Ok(out)
+    }
+
+    /// Lists the entries of a directory on the host file system, sorted by name.
+    async fn list_directory(
+        &mut self,
+        path: String,
+    ) -> Result<Vec<filesystem::DirectoryEntry>, filesystem::Error> {
+        let p = self.resolve(&path)?;
+
+        let fail = |err| fs_error(err, format!("failed to list directory '{path}'"));
+
+        let mut out = vec![];
+
+        for entry in std::fs::read_dir(p).map_err(fail)? {
+            let entry = entry.map_err(fail)?;
+
+            out.push(filesystem::DirectoryEntry {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                kind: entry.file_type().map_err(fail)?.into(),
+            });
+        }
+
+        out.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(out)
+    }
+
+    /// Returns the metadata of a file or directory on the host file system.
+    async fn metadata(
+        &mut self,
+        path: String,
+    ) -> Result<filesystem::EntryMetadata, filesystem::Error> {
+        let p = self.resolve(&path)?;
+
+        let fail = |err| fs_error(err, format!("failed to read metadata of '{path}'"));
+
+        let md = std::fs::metadata(p).map_err(fail)?;
+
+        let modified = md
+            .modified()
+            .map_err(fail)?
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+
+        Ok(filesystem::EntryMetadata {
+            kind: md.file_type().into(),
+            size: md.len(),
+            modified,
+        })
+    }
+
+    /// Checks whether a file or directory exists on the host file system.
+    async fn exists(&mut self, path: String) -> Result<bool, filesystem::Error> {
+        let p = self.resolve(&path)?;
+
+        p.try_exists()
+            .map_err(|err| fs_error(err, format!("failed to check if '{path}' exists")))
+    }
+
+    /// Removes a file from the host file system.
+    async fn remove_file(&mut self, path: String) -> Result<(), filesystem::Error> {
+        let p = self.resolve_entry(&path)?;
+
+        std::fs::remove_file(p)
+            .map_err(|err| fs_error(err, format!("failed to remove file '{path}'")))?;
+
+        Ok(())
+    }
+
+    /// Removes a directory (and its contents, if `recursive` is set) from the host file system.
+    async fn remove_directory(
+        &mut self,
+        path: String,
+        recursive: bool,
+    ) -> Result<(), filesystem::Error> {
+        let p = self.resolve_entry(&path)?;
+
+        // The sandbox roots themselves are off limits
+        if self.sandbox.roots().contains(&p) {
+            return Err(filesystem::Error::AccessDenied(format!(
+                "extension {} is not allowed to remove '{}'",
+                self.name,
+                p.display()
+            )));
+        }
+
+        // A link to a directory is removed itself, its target is left untouched
+        let is_link = std::fs::symlink_metadata(&p).is_ok_and(|m| m.file_type().is_symlink());
+
+        let out = match (is_link, recursive) {
+            (true, _) => remove_link(&p),
+            (false, true) => std::fs::remove_dir_all(p),
+            (false, false) => std::fs::remove_dir(p),
+        };
+
+        out.map_err(|err| fs_error(err, format!("failed to remove directory '{path}'")))?;
+
+        Ok(())
+    }
+
+    /// Renames a file or directory on the host file system.
+    async fn rename(
+        &mut self,
+        source: String,
+        destination: String,
+    ) -> Result<(), filesystem::Error> {
+        let (from, to) = (
+            self.resolve_entry(&source)?,
+            self.resolve_entry(&destination)?,
+        );
+
+        std::fs::rename(from, to).map_err(|err| {
+            fs_error(
+                err,
+                format!("failed to rename '{source}' to '{destination}'"),
+            )
+        })?;
+
+        Ok(())
+    }
+
+    /// Copies a file on the host file system.
+    async fn copy(
+        &mut self,
+        source: String,
+        destination: String,
+    ) -> Result<u64, filesystem::Error> {
+        let (from, to) = (self.resolve(&source)?, self.resolve_entry(&destination)?);
+
+        // Replace a link at the destination rather than writing through it
+        if std::fs::symlink_metadata(&to).is_ok_and(|m| m.file_type().is_symlink()) {
+            remove_link(&to).map_err(|err| {
+                fs_error(err, format!("failed to copy '{source}' to '{destination}'"))
+            })?;
+        }
+
+        std::fs::copy(from, to)
+            .map_err(|err| fs_error(err, format!("failed to copy '{source}' to '{destination}'")))
+    }
+
+    /// Finds the paths matching a glob pattern on the host file system.
+    async fn glob(&mut self, pattern: String) -> Result<Vec<String>, filesystem::Error> {
+        let out = self
+            .sandbox
+            .glob(&pattern)
+            .map_err(|err| self.sandbox_error(err))?;
+
+        Ok(out
+            .into_iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect())
+    }
+}
+
+/// Removes a symlink itself, whatever its target
+fn remove_link(p: &Path) -> std::io::Result<()> {
+    // Windows directory links must be removed as directories
+    std::fs::remove_file(p).or_else(|err| match cfg!(windows) {
+        true => std::fs::remove_dir(p),
+        false => Err(err),
+    })
+}
+
+impl From<std::fs::FileType> for filesystem::EntryKind {
+    fn from(value: std::fs::FileType) -> Self {
+        match value {
+            t if t.is_file() => Self::File,
+            t if t.is_dir() => Self::Directory,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Host implementation for the `icp:cli/environment` interface.
+/// Only the variables allowed for the extension in the manifest are exposed.
+impl environment::Host for ExtensionHost {
+    async fn get_var(&mut self, name: String) -> Option<String> {
+        if !self.permissions.allows_variable(&name) {
+            return None;
+        }
+
+        var(name).ok()
+    }
+
+    async fn vars(&mut self) -> Vec<(String, String)> {
+        let mut out = self.allowed_vars();
+        out.sort();
+        out
+    }
+
+    async fn current_dir(&mut self) -> String {
+        current_dir()
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// Host implementation for the `icp:cli/log` interface.
+/// Records are routed through the host's `tracing` subscriber, tagged with the extension's name.
+impl log::Host for ExtensionHost {
+    async fn log(&mut self, level: log::Level, message: String, fields: Vec<(String, String)>) {
+        logging::emit(&self.name, level, &message, &fields);
+    }
+
+    async fn enabled(&mut self, level: log::Level) -> bool {
+        logging::is_enabled(level)
+    }
+}
+
+/// Host implementation for the `icp:cli/storage` interface.
+/// Each extension can only access its own namespace.
+impl storage::Host for ExtensionHost {
+    async fn get(&mut self, key: String) -> Result<Option<Vec<u8>>, String> {
+        self.storage.get(&key).map_err(|err| err.to_string())
+    }
+
+    async fn set(&mut self, key: String, value: Vec<u8>) -> Result<(), String> {
+        self.storage
+            .set(&key, &value)
+            .map_err(|err| err.to_string())
+    }
+
+    async fn delete(&mut self, key: String) -> Result<bool, String> {
+        self.storage.delete(&key).map_err(|err| err.to_string())
+    }
+
+    async fn list_keys(&mut self) -> Result<Vec<String>, String> {
+        self.storage.list_keys().map_err(|err| err.to_string())
+    }
+}
+
+/// Host implementation for the `icp:cli/http` interface.
+/// Only the hosts allowed for the extension in the manifest can be reached.
+impl http::Host for ExtensionHost {
+    async fn send(&mut self, request: http::Request) -> Result<http::Response, http::Error> {
+        let http::Request {
+            method,
+            url,
+            headers,
+            body,
+        } = request;
+
+        let res = self
+            .http
+            .send(Request {
+                method,
+                url,
+                headers,
+                body,
+            })
+            .await
+            .map_err(|err| match err {
+                HttpError::HostNotAllowed(host) => http::Error::HostNotAllowed(format!(
+                    "extension {} is not allowed to reach {host}",
+                    self.name
+                )),
+                err @ HttpError::InvalidRequest(_) => http::Error::InvalidRequest(err.to_string()),
+                err @ HttpError::Timeout(_) => http::Error::Timeout(err.to_string()),
+                err @ HttpError::Transport(_) => http::Error::Transport(err.to_string()),
+            })?;
+
+        Ok(http::Response {
+            status: res.status,
+            headers: res.headers,
+            body: res.body,
+        })
+    }
+}
+
+/// Host implementation for the `icp:cli/terminal` interface.
+/// Prompts fall back to their default answer when the host is not interactive.
+impl terminal::Host for ExtensionHost {
+    async fn is_interactive(&mut self) -> bool {
+        self.terminal.is_interactive()
+    }
+
+    async fn is_terminal(&mut self, s: terminal::Stream) -> bool {
+        tty::is_terminal(s)
+    }
+
+    async fn supports_color(&mut self, s: terminal::Stream) -> bool {
+        tty::supports_color(s)
+    }
+
+    async fn prompt(
+        &mut self,
+        message: String,
+        default: Option<String>,
+    ) -> Result<String, terminal::Error> {
+        Ok(self.terminal.prompt(&message, default)?)
+    }
+
+    async fn confirm(
+        &mut self,
+        message: String,
+        default: Option<bool>,
+    ) -> Result<bool, terminal::Error> {
+        Ok(self.terminal.confirm(&message, default)?)
+    }
+
+    async fn select(
+        &mut self,
+        message: String,
+        items: Vec<String>,
+        default: Option<u32>,
+    ) -> Result<u32, terminal::Error> {
+        let idx = self
+            .terminal
+            .select(&message, &items, default.map(|d| d as usize))?;
+
+        Ok(idx as u32)
+    }
+
+    async fn password(&mut self, message: String) -> Result<String, terminal::Error> {
+        Ok(self.terminal.password(&message)?)
+    }
+}
+
+impl From<PromptError> for terminal::Error {
+    fn from(err: PromptError) -> Self {
+        match err {
+            PromptError::NonInteractive => terminal::Error::NonInteractive,
+            err => terminal::Error::Other(err.to_string()),
+        }
+    }
+}
+
+/// Host implementation for the `progress` resource of the `icp:cli/terminal` interface.
+impl terminal::HostProgress for ExtensionHost {
+    async fn new(
+        &mut self,
+        message: String,
+        total: Option<u64>,
+    ) -> Result<Resource<ProgressBar>, Error> {
+        Ok(self.table.push(self.terminal.progress(message, total))?)
+    }
+
+    async fn set_message(&mut self, p: Resource<ProgressBar>, message: String) {
+        if let Ok(p) = self.table.get(&p) {
+            p.set_message(message);
+        }
+    }
+
+    async fn inc(&mut self, p: Resource<ProgressBar>, delta: u64) {
+        if let Ok(p) = self.table.get(&p) {
+            p.inc(delta);
+        }
+    }
+
+    async fn set_position(&mut self, p: Resource<ProgressBar>, position: u64) {
+        if let Ok(p) = self.table.get(&p) {
+            p.set_position(position);
+        }
+    }
+
+    async fn finish(&mut self, p: Resource<ProgressBar>, message: Option<String>) {
+        if let Ok(p) = self.table.get(&p) {
+            match message {
+                Some(message) => p.finish_with_message(message),
+                None => p.finish(),
+            }
+        }
+    }
+
+    async fn drop(&mut self, p: Resource<ProgressBar>) -> Result<(), Error> {
+        let p = self.table.delete(p)?;
+
+        if !p.is_finished() {
+            p.finish_and_clear();
+        }
+
+        Ok(())
+    }
+}
+
+/// Host implementation for the `icp:cli/command` interface.
+/// Allows extensions to execute commands on the host system.
+impl command::Host for ExtensionHost {
+    /// Executes a command with the given arguments on the host.
+    /// Returns the standard output, standard error, and exit code.
+    /// If the process is terminated by a signal and has no exit code,
+    /// a default exit code of 1 is returned.
+    /// Only the commands allowed for the extension in the manifest can be executed.
+    async fn execute(
+        &mut self,
+        command: String,
+        args: Vec<String>,
+    ) -> Result<CommandOutput, String> {
+        use std::process::Command;
+
+        self.check_command(&command)?;
+
+        let out = Command::new(find_program(&command))
+            .args(args)
+            .env_clear()
+            .envs(self.command_vars())
+            .output()
+            .map_err(|err| format!("failed to execute command: {err}"))?;
+
+        Ok(CommandOutput {
+            stdout: out.stdout,
+            stderr: out.stderr,
+            exit_code: out.status.code().unwrap_or(1) as u32,
+        })
+    }
+
+    /// Spawns a command with the given arguments and options on the host.
+    /// The working directory must lie within the extension's filesystem sandbox.
+    /// Only the commands allowed for the extension in the manifest can be spawned.
+    async fn spawn(
+        &mut self,
+        command: String,
+        args: Vec<String>,
+        options: SpawnOptions,
+    ) -> Result<Resource<Process>, String> {
+        self.check_command(&command)?;
+
+        let cwd = options
+            .cwd
+            .as_deref()
+            .map(|cwd| self.resolve(cwd))
+            .transpose()
+            .map_err(|err| format!("invalid working directory: {err}"))?;
+
+        let p = Process::spawn(&command, &args, cwd, self.command_vars(), options)
+            .map_err(|err| format!("failed to spawn command '{command}': {err:#}"))?;
+
+        self.table
+            .push(p)
+            .map_err(|err| format!("failed to store process: {err}"))
+    }
+}
+
+/// Host implementation for the `process` resource of the `icp:cli/command` interface.
+impl command::HostProcess for ExtensionHost {
+    async fn write_stdin(&mut self, p: Resource<Process>, data: Vec<u8>) -> Result<(), String> {
+        let p = self.table.get_mut(&p).map_err(|err| err.to_string())?;
+
+        p.write_stdin(&data).await.map_err(|err| format!("{err:#}"))
+    }
+
+    async fn close_stdin(&mut self, p: Resource<Process>) {
+        if let Ok(p) = self.table.get_mut(&p) {
+            p.close_stdin();
+        }
+    }
+
+    async fn read(&mut self, p: Resource<Process>) -> Option<Output> {
+        self.table.get_mut(&p).ok()?.read().await
+    }
+
+    async fn wait(&mut self, p: Resource<Process>) -> Result<u32, String> {
+        let p = self.table.get_mut(&p).map_err(|err| err.to_string())?;
+
+        p.wait().await.map_err(|err| format!("{err:#}"))
+    }
+
+    async fn drop(&mut self, p: Resource<Process>) -> Result<(), Error> {
+        self.table.delete(p)?;
+
+        Ok(())
+    }
+}
+
+/// Completes the names of installed extensions, read from the manifest when completing
+fn complete_extension_names(mh: ManifestHandle) -> ArgValueCompleter {
+    ArgValueCompleter::new(move |current: &OsStr| {
+        let (Some(current), Ok(m)) = (current.to_str(), mh.load()) else {
+            return vec![];
+        };
+
+        m.xs.iter()
+            .filter(|x| x.name.starts_with(current))
+            .map(|x| CompletionCandidate::new(&x.name))
+            .collect()
+    })
+}
+
+/// Arguments granting host capabilities to an extension, for `extension add` and `extension install`
+fn permission_args() -> [Arg; 4] {
+    [
+        Arg::new("allow-command")
+            .long("allow-command")
+            .value_name("COMMAND")
+            .action(ArgAction::Append)
+            .help("Allow the extension to execute a command on the host"),
+
+        Arg::new("allow-env")
+            .long("allow-env")
+            .value_name("VAR")
+            .action(ArgAction::Append)
+            .help("Allow the extension to read an environment variable (or a prefix ending in '*')"),
+
+        Arg::new("allow-host")
+            .long("allow-host")
+            .value_name("HOST")
+            .action(ArgAction::Append)
+            .help("Allow the extension to send HTTP requests to a host (e.g. 'ic0.app', '*.icp0.io' or 'localhost:4943')"),
+
+        Arg::new("allow-dir")
+            .long("allow-dir")
+            .value_name("DIR")
+            .action(ArgAction::Append)
+            .value_parser(value_parser!(PathBuf))
+            .help("Grant the extension access to a directory outside of the workspace"),
+    ]
+}
+
+/// Renders the outcome of a host command: a message in text format, or a structured value
+fn report(format: OutputFormat, message: String, value: serde_json::Value) -> String {
+    match format {
+        OutputFormat::Text => output::render(&serde_json::Value::String(message), format),
+        OutputFormat::Json => output::render(&value, format),
+    }
+}
+
+/// Reads the host capabilities granted with [`permission_args`]
+fn granted_permissions(ms: &ArgMatches) -> Result<Permissions, Error> {
+    let strings = |id: &str| -> Vec<String> {
+        ms.get_many::<String>(id)
+            .unwrap_or_default()
+            .cloned()
+            .collect()
+    };
+
+    Ok(Permissions {
+        directories: ms
+            .get_many::<PathBuf>("allow-dir")
+            .unwrap_or_default()
+            .map(|p| absolute(p).context("failed to resolve granted directory"))
+            .collect::<Result<_, _>>()?,
+
+        commands: strings("allow-command"),
+        variables: strings("allow-env"),
+        hosts: strings("allow-host"),
+    })
+}
+
+/// Argument selecting the registry index, for `extension search` and `extension install`
+fn registry_arg() -> Arg {
+    Arg::new("registry")
+        .long("registry")
+        .value_name("URL")
+        .env(ENV_REGISTRY)
+        .default_value(DEFAULT_INDEX_URL)
+        .help("Registry index (an HTTP or file:// URL, or a local path)")
+}
+
+// Directory setup helper
+fn _ensure_directories(
+    manifest: &Path,
+    extensions: &Path,
+    precompiles: &Path,
+) -> Result<(), Error> {
+    if let Some(parent) = manifest.parent() {
+        create_dir_all(parent).context("failed to create manifest directory")?;
+    }
+    create_dir_all(extensions).context("failed to create extensions directory")?;
+    create_dir_all(precompiles).context("failed to create precompiles directory")?;
+    Ok(())
+}
+
+/// Instantiates and initializes the given extensions, in order.
+/// The loading order is expected to include all of the extensions' providers.
+async fn load_extensions(
+    ngn: &Engine,
+    m: &Manifest,
+    loading_order: &[String],
+    storage_dir: &Path,
+    terminal: Terminal,
+    output: OutputFormat,
+) -> Result<(WasmStore<State>, DashMap<String, Extension>), Error> {
+    // Linker
+    let mut lnk = Linker::new(ngn);
+
+    // Components (initialize)
+    let cmpnts: DashMap<String, Component> = DashMap::new();
+
+    // Load components in dependency order
+    for name in loading_order {
+        if let Some(extension) = m.xs.iter().find(|x| &x.name == name) {
+            let pre = read(&extension.pre)?;
+
+            let component = unsafe {
+                Component::deserialize(
+                    ngn,  // engine
+                    &pre, // bytes
+                )
+            }?;
+
+            // Extensions built for another version of the host interfaces cannot be linked
+            let ty = component.component_type();
+            let names = ty.imports(ngn).map(|(name, _)| name);
+
+            if let Some(version) =
+                incompatible_host_version(names.chain(ty.exports(ngn).map(|(name, _)| name)))
+            {
+                return Err(ExtensionError::IncompatibleHost {
+                    name: name.to_owned(),
+                    version,
+                }
+                .into());
+            }
+
+            cmpnts.insert(name.clone(), component);
+        }
+    }
+
+    // Create function registry
+    let reg = FunctionRegistry::new();
+    let reg = Arc::new(Mutex::new(reg));
+
+    // Create dynamic linker
+    let mut dynlnk = DynamicLinker::new(Arc::clone(&reg));
+
+    // Collect unique interfaces
+    let mut ifaces: HashMap<String, Interface> = HashMap::new();
+    for name in loading_order {
+        if let Some(extension) = m.xs.iter().find(|x| &x.name == name) {
             for iface in extension.imports.iter().chain(extension.exports.iter()) {
                 ifaces
                     .entry(iface.name.clone())
@@ -400,24 +1247,8 @@ async fn main() -> Result<(), Error> {
         ifaces.into_values().collect(), // interfaces
     )?;
 
-    // Host imports
-    misc::add_to_linker(
-        &mut lnk,                  // linker
-        |state: &mut State| state, // get
-    )?;
-
-    filesystem::add_to_linker(
-        &mut lnk,                  // linker
-        |state: &mut State| state, // get
-    )?;
-
-    command::add_to_linker(
-        &mut lnk,                  // linker
-        |state: &mut State| state, // get
-    )?;
-
     // NOTE: Well this is gonna be annoying - are we going to have to keep this version up to date??
-    lnk.instance("icp:cli/component@0.3.4")?.func_new_async(
+    lnk.instance("icp:cli/component@0.5.0")?.func_new_async(
         "invoke",
         move |mut store, params: &[WasmVal], results: &mut [WasmVal]| {
             Box::new({
@@ -444,168 +1275,711 @@ async fn main() -> Result<(), Error> {
                         None => bail!("missing function_name parameter for invoke host function"),
                     };
 
-                    let function_name = match function_name {
-                        WasmVal::String(s) => s,
-                        _ => bail!("function_name has the wrong type: {function_name:?}"),
-                    };
+                    let function_name = match function_name {
+                        WasmVal::String(s) => s,
+                        _ => bail!("function_name has the wrong type: {function_name:?}"),
+                    };
+
+                    // nested parameters
+                    let nparams_raw_val = match params.get(NESTED_PARAMS_IDX) {
+                        Some(v) => v,
+                        None => bail!("missing nested params for invoke host function"),
+                    };
+
+                    // Convert raw WasmVal (expected to be List of U8) to Vec<u8> using the helper
+                    let nparams_bytes =
+                        match component_invoke::try_wasm_list_u8_to_vec_u8(nparams_raw_val) {
+                            Ok(bytes) => bytes,
+                            Err(e) => bail!(e),
+                        };
+
+                    // Lookup function using function registry
+                    let f = match reg.lock().unwrap().lookup(interface_name, function_name) {
+                        // Found function
+                        Ok(Some(f)) => Ok(f),
+
+                        // Register, but not resolved
+                        Ok(None) => Err(format!(
+                            "function {}:{} is registered by not yet resolved",
+                            interface_name, function_name
+                        )),
+
+                        // Not found
+                        Err(FunctionRegistryError::NotFound(key)) => Err(format!(
+                            "function {}:{} (key: {}) not found in registry",
+                            interface_name, function_name, key
+                        )),
+
+                        // Other errors
+                        Err(err) => Err(format!(
+                            "Error during function lookup for {}:{}: {}",
+                            interface_name, function_name, err,
+                        )),
+                    };
+
+                    let f = match f {
+                        // Ok
+                        Ok(f) => f,
+
+                        // Fail
+                        Err(err) => {
+                            results[0] = WasmVal::Result(Err(Some(Box::new(WasmVal::String(err)))));
+
+                            return Ok(());
+                        }
+                    };
+
+                    // Call the extracted function to execute the component function
+                    let provider = reg
+                        .lock()
+                        .unwrap()
+                        .provider(&FunctionRegistry::create_key(interface_name, function_name))
+                        .unwrap_or_default()
+                        .to_owned();
+
+                    State::enter(store.as_context_mut(), &provider, function_name)?;
+
+                    let out =
+                        component_invoke::execute_component_function(&mut store, &f, nparams_bytes)
+                            .await;
+
+                    match State::complete(store.as_context_mut(), out) {
+                        Ok(wasm_u8_list) => {
+                            results[0] =
+                                WasmVal::Result(Ok(Some(Box::new(WasmVal::List(wasm_u8_list)))));
+                        }
+                        // Limit breaches trap the caller as well
+                        Err(e) if e.downcast_ref::<LimitExceeded>().is_some() => return Err(e),
+
+                        Err(e) => {
+                            // Convert anyhow::Error to a WasmVal::String for the error part of WasmVal::Result
+                            results[0] = WasmVal::Result(Err(Some(Box::new(WasmVal::String(
+                                e.to_string(),
+                            )))));
+                        }
+                    }
+
+                    Ok(())
+                }
+            })
+        },
+    )?;
+
+    // Workspace
+    let cwd = current_dir().context("failed to get current directory")?;
+    let root = find_workspace_root(&cwd);
+
+    // Randomness (deterministic when a test seed is set, honored in debug builds only)
+    let seed = var(ENV_TEST_SEED).ok().filter(|_| {
+        if cfg!(debug_assertions) {
+            tracing::warn!("{ENV_TEST_SEED} is set, extensions receive predictable randomness");
+            true
+        } else {
+            tracing::warn!("{ENV_TEST_SEED} is ignored in release builds");
+            false
+        }
+    });
+
+    // Host state (one per extension)
+    let hosts = loading_order
+        .iter()
+        .map(|name| {
+            let (permissions, limits, wasi) =
+                m.xs.iter()
+                    .find(|x| &x.name == name)
+                    .map(|x| (x.permissions.clone(), x.limits.clone(), x.wasi))
+                    .unwrap_or_default();
+
+            let http = HttpClient::new(
+                Allowlist::new(permissions.hosts.iter().cloned()), // allowlist
+                limits.http_timeout(),                             // timeout
+            );
+
+            let wasi = match wasi {
+                true => Some(wasi_ctx(name, &root, &cwd, &permissions, output)?),
+                false => None,
+            };
+
+            Ok(ExtensionHost {
+                name: name.to_owned(),
+                sandbox: Sandbox::new(
+                    &cwd,
+                    once(root.clone()).chain(permissions.directories.iter().cloned()),
+                ),
+                permissions,
+                table: ResourceTable::new(),
+                random: match &seed {
+                    Some(seed) => Random::seeded(seed, name),
+                    None => Random::system(),
+                },
+                limits,
+                storage: Storage::new(storage_dir, name),
+                http,
+                wasi,
+                terminal,
+                output,
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+
+    // Store
+    let mut store = WasmStore::new(
+        ngn, // engine
+        State {
+            hosts,
+            calls: vec![],
+        }, // data
+    );
+
+    store.limiter(|state| state);
+
+    // Components (instantiate)
+    let insts: DashMap<String, Extension> = DashMap::new();
+
+    // Instantiate components in dependency order
+    for (idx, name) in loading_order.iter().enumerate() {
+        let cmpnt = cmpnts
+            .get(name)
+            .ok_or_else(|| anyhow!("missing component"))?;
+
+        // Host imports (bound to the extension's own host state)
+        let mut lnk = lnk.clone();
+
+        misc::add_to_linker(
+            &mut lnk,                                       // linker
+            move |state: &mut State| &mut state.hosts[idx], // get
+        )?;
+
+        filesystem::add_to_linker(
+            &mut lnk,                                       // linker
+            move |state: &mut State| &mut state.hosts[idx], // get
+        )?;
+
+        command::add_to_linker(
+            &mut lnk,                                       // linker
+            move |state: &mut State| &mut state.hosts[idx], // get
+        )?;
+
+        environment::add_to_linker(
+            &mut lnk,                                       // linker
+            move |state: &mut State| &mut state.hosts[idx], // get
+        )?;
+
+        log::add_to_linker(
+            &mut lnk,                                       // linker
+            move |state: &mut State| &mut state.hosts[idx], // get
+        )?;
+
+        storage::add_to_linker(
+            &mut lnk,                                       // linker
+            move |state: &mut State| &mut state.hosts[idx], // get
+        )?;
+
+        http::add_to_linker(
+            &mut lnk,                                       // linker
+            move |state: &mut State| &mut state.hosts[idx], // get
+        )?;
+
+        terminal::add_to_linker(
+            &mut lnk,                                       // linker
+            move |state: &mut State| &mut state.hosts[idx], // get
+        )?;
+
+        // WASI (only for extensions that import it)
+        if store.data().hosts[idx].wasi.is_some() {
+            wasmtime_wasi::add_to_linker_async(&mut lnk)?;
+        }
+
+        // Component (generic)
+        State::enter(store.as_context_mut(), name, "instantiate")?;
+
+        let inst = lnk
+            .instantiate_async(
+                &mut store,    // store
+                cmpnt.value(), // component
+            )
+            .await;
+
+        let inst = State::complete(store.as_context_mut(), inst)?;
+
+        // Resolve exports for this extension
+        if let Some(x) = m.xs.iter().find(|x| &x.name == name) {
+            dynlnk.resolve(
+                &mut store, // store
+                &x.name,    // extension
+                &inst,      // instance
+                &x.exports, // exports
+            )?;
+        }
+
+        // Component (typed)
+        let inst = Extension::new(
+            &mut store, // store
+            &inst,      // instance
+        )?;
+
+        insts.insert(
+            name.to_owned(), // key
+            inst,            // value
+        );
+    }
+
+    // Extensions (init)
+    for name in loading_order {
+        let inst = insts
+            .get(name)
+            .context("extension not initialized properly")?;
+
+        State::enter(store.as_context_mut(), name, "init")?;
+
+        let out = inst.icp_cli_init().call_init(&mut store).await;
+
+        let out = match State::complete(store.as_context_mut(), out) {
+            Ok(out) => out,
+            Err(err) => Err(format!("{err:?}")),
+        };
+
+        out.map_err(|reason| ExtensionError::InitFailed {
+            name: name.to_owned(),
+            reason,
+        })?;
+    }
+
+    Ok((store, insts))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(exit_code) => exit_code,
+        Err(err) => {
+            eprintln!("Error: {err:?}");
+
+            let exit_code = match err.downcast_ref::<ExtensionError>() {
+                Some(err) => err.exit_code(),
+                None => EXIT_CODE_FAILURE,
+            };
+
+            ExitCode::from(exit_code)
+        }
+    }
+}
+
+async fn run() -> Result<ExitCode, Error> {
+    // Command
+    let c = Command::new(SERVICE_NAME);
+
+    // Version
+    let c = c.version("1.0.0");
+
+    // Arg (manifest)
+    let c = c.arg(
+        Arg::new("manifest")
+            .short(ARG_SHORT_MANIFEST)
+            .long(ARG_LONG_MANIFEST)
+            .default_value(DEFAULT_PATH_MANIFEST.as_os_str())
+            .value_parser(value_parser!(PathBuf)),
+    );
+
+    // Load Manifest
+    let args = args_os().collect::<Vec<_>>();
+
+    let mpath = args.windows(2).find(|&p| {
+        [
+            format!("-{ARG_SHORT_MANIFEST}"),
+            format!("--{ARG_LONG_MANIFEST}"),
+        ]
+        .iter()
+        .any(|f| *f.as_str() == p[0])
+    });
+
+    let mut itr = vec![
+        OsString::from_str(c.get_name())?, // bin
+    ];
+
+    if let Some(mpath) = mpath {
+        itr.append(&mut mpath.to_vec());
+    }
+
+    let ms = c.clone().get_matches_from(itr);
+
+    let mpath = ms
+        .get_one::<PathBuf>("manifest")
+        .context("missing manifest path")?;
+
+    // Manifest (handle)
+    let mh = ManifestHandle(mpath.to_owned());
+
+    // Setup
+    let c = c
+        .disable_help_subcommand(true)
+        .disable_version_flag(true)
+        .arg_required_else_help(true);
+
+    // Args (verbosity, placed before the subcommand)
+    let c = c
+        .arg(
+            Arg::new("verbose")
+                .short(ARG_SHORT_VERBOSE)
+                .long(ARG_LONG_VERBOSE)
+                .action(ArgAction::Count)
+                .help("Increase logging verbosity (repeat for more)"),
+        )
+        .arg(
+            Arg::new("quiet")
+                .short(ARG_SHORT_QUIET)
+                .long(ARG_LONG_QUIET)
+                .action(ArgAction::Count)
+                .help("Decrease logging verbosity (repeat for less)"),
+        );
+
+    // Arg (yes, placed before the subcommand)
+    let c = c.arg(
+        Arg::new("yes")
+            .short(ARG_SHORT_YES)
+            .long(ARG_LONG_YES)
+            .action(ArgAction::SetTrue)
+            .help("Do not prompt, use default answers instead"),
+    );
+
+    // Arg (output, placed before the subcommand)
+    let c = c.arg(
+        Arg::new("output")
+            .long(ARG_LONG_OUTPUT)
+            .value_parser(
+                PossibleValuesParser::new(["text", "json"]).try_map(|s| s.parse::<OutputFormat>()),
+            )
+            .default_value("text")
+            .help("Format of command results"),
+    );
+
+    // Arg (extensions-dir)
+    let c = c.arg(
+        Arg::new("extensions-dir")
+            .long(ARG_LONG_EXTENSIONS)
+            .default_value(DEFAULT_DIR_EXTENSIONS.as_os_str())
+            .value_parser(value_parser!(PathBuf)),
+    );
+
+    // Arg (precompiles-dir)
+    let c = c.arg(
+        Arg::new("precompiles-dir")
+            .long(ARG_LONG_PRECOMPILES)
+            .default_value(DEFAULT_DIR_PRECOMPILES.as_os_str())
+            .value_parser(value_parser!(PathBuf)),
+    );
+
+    // Arg (storage-dir)
+    let c = c.arg(
+        Arg::new("storage-dir")
+            .long(ARG_LONG_STORAGE)
+            .default_value(DEFAULT_DIR_STORAGE.as_os_str())
+            .value_parser(value_parser!(PathBuf)),
+    );
+
+    // Extension
+    let c = c.subcommand(
+        Command::new("extension")
+            .about("manage extensions")
+            .subcommand_required(true)
+            .subcommand(
+                Command::new("ls").alias("list").arg(
+                    Arg::new("long")
+                        .long("long")
+                        .short('l')
+                        .action(ArgAction::SetTrue)
+                        .help("Show version, source, install time, checksum and sizes"),
+                ),
+            )
+            .subcommand(
+                Command::new("add")
+                    .arg(Arg::new("name").long("name").required(true))
+                    .arg(Arg::new("uri").help("Local path or Uri").required(true))
+                    .arg(
+                        Arg::new("checksum")
+                            .long("checksum")
+                            .value_name("SHA256")
+                            .help("Expected SHA256 checksum for verification"),
+                    )
+                    .arg(
+                        Arg::new("force")
+                            .long("force")
+                            .action(ArgAction::SetTrue)
+                            .help("Overwrite existing extension"),
+                    )
+                    .args(permission_args()),
+            )
+            .subcommand(
+                Command::new("search")
+                    .about("Search the registry for extensions")
+                    .arg(
+                        Arg::new("query")
+                            .help("Text to look for in extension names and descriptions"),
+                    )
+                    .arg(registry_arg()),
+            )
+            .subcommand(
+                Command::new("install")
+                    .about("Install an extension from the registry")
+                    .arg(
+                        Arg::new("extension")
+                            .value_name("NAME[@VERSION]")
+                            .required(true)
+                            .help("Extension to install, optionally with a version requirement (e.g. 'build@1.2.0' or 'build@^1.2')"),
+                    )
+                    .arg(
+                        Arg::new("force")
+                            .long("force")
+                            .action(ArgAction::SetTrue)
+                            .help("Overwrite existing extension"),
+                    )
+                    .arg(registry_arg())
+                    .args(permission_args()),
+            )
+            .subcommand(
+                Command::new("update")
+                    .about("Update extensions from their registry or source")
+                    .arg(
+                        Arg::new("name")
+                            .help("Extension to update")
+                            .required_unless_present("all")
+                            .conflicts_with("all")
+                            .add(complete_extension_names(mh.clone())),
+                    )
+                    .arg(
+                        Arg::new("all")
+                            .long("all")
+                            .action(ArgAction::SetTrue)
+                            .help("Update all extensions"),
+                    )
+                    .arg(
+                        Arg::new("major")
+                            .long("major")
+                            .action(ArgAction::SetTrue)
+                            .help("Allow updates to incompatible versions (e.g. from 1.x to 2.0)"),
+                    ),
+            )
+            .subcommand(
+                Command::new("rm")
+                    .alias("remove")
+                    .arg(Arg::new("keep").short('k').action(ArgAction::SetTrue))
+                    .arg(
+                        Arg::new("name")
+                            .required(true)
+                            .add(complete_extension_names(mh.clone())),
+                    ),
+            )
+            .subcommand(
+                Command::new("deps")
+                    .about("Show extension dependencies")
+                    .arg(
+                        Arg::new("name")
+                            .help("Extension name")
+                            .required(false)
+                            .add(complete_extension_names(mh.clone())),
+                    )
+                    .arg(
+                        Arg::new("format")
+                            .long("format")
+                            .value_parser(["tree", "dot", "json"])
+                            .default_value("tree")
+                            .help("Output format"),
+                    )
+                    .arg(
+                        Arg::new("validate")
+                            .long("validate")
+                            .action(ArgAction::SetTrue)
+                            .help("Validate dependencies"),
+                    ),
+            ),
+    );
+
+    // Subcommand (completions)
+    let c = c.subcommand(
+        Command::new("completions")
+            .about("Generate a shell completion script")
+            .after_help("Load the script in your shell's configuration, e.g. `source <(icp completions bash)`")
+            .arg(
+                Arg::new("shell")
+                    .required(true)
+                    .value_parser(["bash", "zsh", "fish", "elvish"]),
+            ),
+    );
+
+    // Subcommand (docs)
+    let c = c.subcommand(
+        Command::new("docs")
+            .about("Generate reference pages for all commands, including installed extensions")
+            .arg(
+                Arg::new("format")
+                    .long("format")
+                    .value_parser(["man", "markdown"])
+                    .default_value("man")
+                    .help("Page format"),
+            )
+            .arg(
+                Arg::new("out")
+                    .long("out")
+                    .required(true)
+                    .value_parser(value_parser!(PathBuf))
+                    .help("Directory to write the pages to (one per command)"),
+            ),
+    );
+
+    // Manifest (load)
+    let mut m = mh.load().or_else(|err| match err {
+        LoadError::NotFound(_) => {
+            // `--yes` is looked up directly, since arguments are only parsed once extensions are known
+            let yes = args.iter().any(|arg| {
+                [format!("-{ARG_SHORT_YES}"), format!("--{ARG_LONG_YES}")]
+                    .iter()
+                    .any(|f| *f.as_str() == *arg)
+            });
+
+            let create = Terminal::new(yes)
+                .confirm(
+                    &format!(
+                        "No extensions manifest found at {}, create it?",
+                        mpath.display()
+                    ),
+                    Some(true),
+                )
+                .context("failed to confirm manifest creation")?;
+
+            if !create {
+                bail!("extensions manifest not found at {}", mpath.display());
+            }
 
-                    // nested parameters
-                    let nparams_raw_val = match params.get(NESTED_PARAMS_IDX) {
-                        Some(v) => v,
-                        None => bail!("missing nested params for invoke host function"),
-                    };
+            let m = Manifest::default();
 
-                    // Convert raw WasmVal (expected to be List of U8) to Vec<u8> using the helper
-                    let nparams_bytes =
-                        match component_invoke::try_wasm_list_u8_to_vec_u8(nparams_raw_val) {
-                            Ok(bytes) => bytes,
-                            Err(e) => bail!(e),
-                        };
+            mh.store(&m)
+                .context("failed to store initial extensions manifest")?;
 
-                    // Lookup function using function registry
-                    let f = match reg.lock().unwrap().lookup(interface_name, function_name) {
-                        // Found function
-                        Ok(Some(f)) => Ok(f),
+            Ok(m)
+        }
 
-                        // Register, but not resolved
-                        Ok(None) => Err(format!(
-                            "function {}:{} is registered by not yet resolved",
-                            interface_name, function_name
-                        )),
+        //
+        _ => Err(err.into()),
+    })?;
 
-                        // Not found
-                        Err(FunctionRegistryError::NotFound(key)) => Err(format!(
-                            "function {}:{} (key: {}) not found in registry",
-                            interface_name, function_name, key
-                        )),
+    // Create dependency graph and resolve loading order
+    let dependency_graph = DependencyGraph::new(&m).context("failed to create dependency graph")?;
 
-                        // Other errors
-                        Err(err) => Err(format!(
-                            "Error during function lookup for {}:{}: {}",
-                            interface_name, function_name, err,
-                        )),
-                    };
+    // Check for circular dependencies
+    if dependency_graph.has_cycles() {
+        eprintln!("Warning: Circular dependencies detected in extensions:");
+        eprintln!("{}", dependency_graph.format_cycles());
+        eprintln!("Some extensions may not function correctly.");
+    }
 
-                    let f = match f {
-                        // Ok
-                        Ok(f) => f,
+    // Validate dependencies
+    if let Err(err) = dependency_graph.validate_dependencies(&m) {
+        eprintln!("Warning: Dependency validation failed: {}", err);
+        eprintln!("Some extensions may not function correctly.");
+    }
 
-                        // Fail
-                        Err(err) => {
-                            results[0] =
-                                WasmVal::Result(Err(Some(Box::new(WasmVal::String(err.into())))));
+    // WASM Configuration
+    let mut cfg = Config::new();
+    let cfg = cfg.async_support(true).consume_fuel(true);
 
-                            return Ok(());
-                        }
-                    };
+    // Engine
+    let ngn = Engine::new(cfg)?;
 
-                    // Call the extracted function to execute the component function
-                    match component_invoke::execute_component_function(
-                        &mut store,
-                        &f,
-                        nparams_bytes,
-                    )
-                    .await
-                    {
-                        Ok(wasm_u8_list) => {
-                            results[0] =
-                                WasmVal::Result(Ok(Some(Box::new(WasmVal::List(wasm_u8_list)))));
-                        }
-                        Err(e) => {
-                            // Convert anyhow::Error to a WasmVal::String for the error part of WasmVal::Result
-                            results[0] = WasmVal::Result(Err(Some(Box::new(WasmVal::String(
-                                e.to_string().into(),
-                            )))));
-                        }
-                    }
+    // Precompiles (recompile precompiles produced by an incompatible engine)
+    let mut updated = false;
+    let mut stale = HashSet::new();
 
-                    Ok(())
-                }
-            })
-        },
-    )?;
+    let h = compatibility_hash(&ngn);
 
-    // Store
-    let mut store = WasmStore::new(
-        &ngn,  // engine
-        State, // data
-    );
+    for x in m.xs.iter_mut() {
+        if x.compatibility_hash.as_ref() == Some(&h) {
+            continue;
+        }
 
-    // Components (instantiate)
-    let insts: DashMap<String, Extension> = DashMap::new();
+        match recompile(&ngn, x) {
+            Ok(()) => updated = true,
 
-    // Instantiate components in dependency order
-    for name in &loading_order {
-        let cmpnt = cmpnts
-            .get(name)
-            .ok_or_else(|| anyhow!("missing component"))?;
+            Err(err) => {
+                eprintln!("Warning: Failed to recompile extension {}: {err}", x.name);
+                eprintln!("Reinstall the extension to make it available.");
+                stale.insert(x.name.clone());
+            }
+        }
+    }
 
-        // Component (generic)
-        let inst = lnk
-            .instantiate_async(
-                &mut store,    // store
-                cmpnt.value(), // component
+    // Specs (cache specs of extensions installed before specs were cached)
+    for x in
+        m.xs.iter_mut()
+            .filter(|x| x.spec.is_none() && !stale.contains(&x.name))
+    {
+        let pre = read(&x.pre)?;
+
+        let cmpnt = unsafe {
+            Component::deserialize(
+                &ngn, // engine
+                &pre, // bytes
             )
-            .await?;
+        }?;
 
-        // Resolve exports for this extension
-        if let Some(x) = m.xs.iter().find(|x| &x.name == name) {
-            dynlnk.resolve(
-                &mut store, // store
-                &x.name,    // extension
-                &inst,      // instance
-                &x.exports, // exports
-            )?;
-        }
+        let spec = fetch_spec(&ngn, &cmpnt)
+            .await
+            .and_then(|spec| Ok(CommandSpec::parse(&spec)?));
 
-        // Component (typed)
-        let inst = Extension::new(
-            &mut store, // store
-            &inst,      // instance
-        )?;
+        match spec {
+            Ok(spec) => {
+                x.spec = Some(spec);
+                updated = true;
+            }
 
-        insts.insert(
-            name.to_owned(), // key
-            inst,            // value
-        );
+            Err(err) => {
+                eprintln!(
+                    "Warning: Extension {} has an invalid command spec: {err}",
+                    x.name
+                );
+                eprintln!("Reinstall the extension to make it available.");
+            }
+        }
     }
 
-    // Extensions (init)
-    for name in &loading_order {
-        let inst = insts
-            .get(name)
-            .context("extension not initialized properly")?;
-
-        inst.icp_cli_init()
-            .call_init(&mut store)
-            .await?
-            .map_err(|err| anyhow!("failed to initialize extension {name}: {err}"))?;
+    if updated {
+        mh.store(&m)
+            .context("failed to store extensions manifest")?;
     }
 
     // Extensions (hydrate)
     let mut c = c;
 
-    for p in &insts {
-        let (name, inst) = p.pair();
+    for x in m.xs.iter().filter(|x| !stale.contains(&x.name)) {
+        let mut cspec = match &x.spec {
+            Some(cspec) => cspec.clone(),
+            None => continue,
+        };
 
-        // Call spec for CommandSpec
-        let cspec = inst
-            .icp_cli_cli()
-            .call_spec(&mut store)
-            .await
-            .context("failed to retrieve spec")?;
+        // Extensions are only invoked by name
+        cspec.aliases.clear();
 
         c = c.subcommand({
-            let cspec: CommandSpec = serde_json::from_str(&cspec)?;
-            let c: Command = cspec.into();
+            let c = spec::command(cspec, &x.permissions);
 
             // Overwrite name
-            c.name(name)
+            c.name(&x.name)
         });
     }
 
+    // Completions (requested by the script from `icp completions`, which calls back into icp
+    // so that completions always reflect the installed extensions)
+    CompleteEnv::with_factory(|| c.clone())
+        .var(ENV_COMPLETE)
+        .complete();
+
     // Subcommand
-    let ms = c.get_matches();
+    let ms = c.clone().get_matches();
+
+    // Logging
+    logging::init(
+        ms.get_count("verbose"), // verbose
+        ms.get_count("quiet"),   // quiet
+    );
 
     let extdir = ms
         .get_one::<PathBuf>("extensions-dir")
@@ -615,6 +1989,18 @@ async fn main() -> Result<(), Error> {
         .get_one::<PathBuf>("precompiles-dir")
         .context("missing precompiles directory")?;
 
+    let stordir = ms
+        .get_one::<PathBuf>("storage-dir")
+        .context("missing storage directory")?;
+
+    // Terminal
+    let term = Terminal::new(ms.get_flag("yes"));
+
+    // Output format
+    let output = *ms
+        .get_one::<OutputFormat>("output")
+        .context("missing output format")?;
+
     // Extension (Lister)
     let ls = ExtensionLister::new(mh.clone());
 
@@ -622,12 +2008,26 @@ async fn main() -> Result<(), Error> {
     let detector = Arc::new(IfaceDetector);
 
     // Extension (Adder)
-    let add = ExtensionAdder::new(
-        ngn.clone(),    // engine
-        mh.clone(),     // mh
-        extdir.clone(), // extensions_dir
-        predir.clone(), // precompiles_dir
-        detector,       // detector
+    let add = Arc::new(ExtensionAdder::new(
+        ngn.clone(),                    // engine
+        mh.clone(),                     // mh
+        extdir.clone(),                 // extensions_dir
+        predir.clone(),                 // precompiles_dir
+        detector,                       // detector
+        Arc::new(CommandsPrompt(term)), // confirm
+    ));
+
+    // Extension (Installer)
+    let install = ExtensionInstaller::new(
+        Arc::new(IndexFetcher), // fetcher
+        add.clone(),            // add
+    );
+
+    // Extension (Updater)
+    let update = ExtensionUpdater::new(
+        mh.clone(),             // mh
+        add.clone(),            // adder
+        Arc::new(IndexFetcher), // fetcher
     );
 
     // Extension (Remover)
@@ -635,71 +2035,515 @@ async fn main() -> Result<(), Error> {
 
     match ms.subcommand() {
         Some(("extension", ms)) => match ms.subcommand() {
-            Some(("ls", _)) => {
-                let names = ls
+            Some(("ls", ms)) => {
+                let xs = ls
                     .list()
                     .await
                     .context("failed to list installed extensions")?;
 
-                if names.is_empty() {
-                    println!("No extensions installed");
-                } else {
-                    names.iter().for_each(|name| println!("{name}"));
+                match (output, ms.get_flag("long")) {
+                    // Names
+                    (OutputFormat::Text, false) if !xs.is_empty() => {
+                        xs.iter().for_each(|x| println!("{}", x.name));
+                    }
+
+                    (OutputFormat::Text, _) if xs.is_empty() => {
+                        println!("No extensions installed");
+                    }
+
+                    // Details
+                    (format, _) => {
+                        let v = xs.iter().map(|x| describe(x, format)).collect();
+                        println!("{}", output::render(&serde_json::Value::Array(v), format));
+                    }
                 }
             }
 
             Some(("add", ms)) => {
+                let permissions = granted_permissions(ms)?;
+                let name = ms.try_get_one::<String>("name")?.expect("missing name");
+
                 add.add(
-                    ms.try_get_one::<String>("name")?.expect("missing name"), // name
-                    ms.try_get_one::<String>("uri")?.expect("missing uri"),   // uri
-                    ms.get_one::<String>("checksum").map(|s| s.as_str()),     // checksum
-                    ms.get_flag("force"),                                     // force
+                    name,                                                   // name
+                    ms.try_get_one::<String>("uri")?.expect("missing uri"), // uri
+                    ms.get_one::<String>("checksum").map(|s| s.as_str()),   // checksum
+                    ms.get_flag("force"),                                   // force
+                    permissions,                                            // permissions
+                    None,                                                   // release
                 )
                 .await
                 .context("failed to add extension")?;
 
-                println!("Extension added");
+                let version = ls
+                    .list()
+                    .await
+                    .context("failed to list installed extensions")?
+                    .into_iter()
+                    .find(|x| &x.name == name)
+                    .and_then(|x| x.metadata.version);
+
+                let v = serde_json::json!({
+                    "name": name,
+                    "version": version,
+                    "status": "added",
+                });
+
+                println!("{}", report(output, "Extension added".to_owned(), v));
+            }
+
+            Some(("search", ms)) => {
+                let registry = ms.get_one::<String>("registry").expect("missing registry");
+
+                let idx = IndexFetcher
+                    .fetch(registry)
+                    .await
+                    .context("failed to fetch registry index")?;
+
+                let xs = idx.search(ms.get_one::<String>("query").map_or("", |q| q.as_str()));
+
+                match output {
+                    OutputFormat::Text if xs.is_empty() => println!("No extensions found"),
+
+                    OutputFormat::Text => {
+                        let v = xs
+                            .iter()
+                            .map(|x| {
+                                serde_json::json!({
+                                    "name": x.name,
+                                    "version": x.latest().map(|v| v.version.to_string()),
+                                    "description": x.description,
+                                })
+                            })
+                            .collect();
+
+                        println!("{}", output::render(&serde_json::Value::Array(v), output));
+                    }
+
+                    OutputFormat::Json => {
+                        let v = serde_json::to_value(xs).context("failed to serialize results")?;
+                        println!("{}", output::render(&v, output));
+                    }
+                }
+            }
+
+            Some(("install", ms)) => {
+                let arg = ms
+                    .get_one::<String>("extension")
+                    .expect("missing extension");
+
+                let (name, req) = match arg.split_once('@') {
+                    Some((name, req)) => (
+                        name,
+                        Some(parse_version_req(req).context("invalid version requirement")?),
+                    ),
+                    None => (arg.as_str(), None),
+                };
+
+                let version = install
+                    .install(
+                        ms.get_one::<String>("registry").expect("missing registry"), // registry
+                        name,                                                        // name
+                        req.as_ref(),                                                // req
+                        ms.get_flag("force"),                                        // force
+                        granted_permissions(ms)?,                                    // permissions
+                    )
+                    .await
+                    .context("failed to install extension")?;
+
+                let v = serde_json::json!({
+                    "name": name,
+                    "version": version.to_string(),
+                    "status": "installed",
+                });
+
+                println!(
+                    "{}",
+                    report(output, format!("Extension {name} {version} installed"), v)
+                );
+            }
+
+            Some(("update", ms)) => {
+                let names = match ms.get_one::<String>("name") {
+                    Some(name) => vec![name.to_owned()],
+                    None => ls
+                        .list()
+                        .await
+                        .context("failed to list installed extensions")?
+                        .into_iter()
+                        .map(|x| x.name)
+                        .collect(),
+                };
+
+                let (mut results, mut failed) = (vec![], false);
+
+                for name in names {
+                    let out = update.update(&name, ms.get_flag("major")).await;
+
+                    // Keep updating the other extensions when updating all of them
+                    let out = match out {
+                        Err(err) if !ms.get_flag("all") => {
+                            return Err(err).context(format!("failed to update extension {name}"));
+                        }
+
+                        Err(err) => {
+                            eprintln!("failed to update extension {name}: {err:#}");
+                            failed = true;
+
+                            serde_json::json!({
+                                "name": name,
+                                "status": "failed",
+                                "error": format!("{err:#}"),
+                            })
+                        }
+
+                        Ok(out) => {
+                            if output == OutputFormat::Text {
+                                println!("{}", out.summary(&name));
+                            }
+
+                            let mut v = serde_json::Map::new();
+                            v.insert("name".to_owned(), name.into());
+
+                            if let serde_json::Value::Object(fields) = serde_json::to_value(&out)
+                                .context("failed to serialize update result")?
+                            {
+                                v.extend(fields);
+                            }
+
+                            serde_json::Value::Object(v)
+                        }
+                    };
+
+                    results.push(out);
+                }
+
+                if output == OutputFormat::Json {
+                    println!(
+                        "{}",
+                        output::render(&serde_json::Value::Array(results), output)
+                    );
+                }
+
+                if failed {
+                    return Ok(ExitCode::from(EXIT_CODE_FAILURE));
+                }
             }
 
             Some(("rm", ms)) => {
+                let name = ms.try_get_one::<String>("name")?.expect("missing name");
+
+                let version = ls
+                    .list()
+                    .await
+                    .context("failed to list installed extensions")?
+                    .into_iter()
+                    .find(|x| &x.name == name)
+                    .and_then(|x| x.metadata.version);
+
                 rm.remove(
-                    ms.try_get_one::<String>("name")?.expect("missing name"), // name
+                    name, // name
                 )
                 .await
                 .context("failed to remove extension")?;
 
-                println!("Extension removed");
+                let v = serde_json::json!({
+                    "name": name,
+                    "version": version,
+                    "status": "removed",
+                });
+
+                println!("{}", report(output, "Extension removed".to_owned(), v));
+            }
+
+            Some(("deps", ms)) => {
+                let name = ms.get_one::<String>("name").map(|s| s.as_str());
+
+                if let Some(name) = name {
+                    if !dependency_graph.contains(name) {
+                        bail!("extension {name} is not installed");
+                    }
+                }
+
+                // The graph is written as JSON with `--output json`, unless a format is given
+                let format = match ms.value_source("format") {
+                    Some(ValueSource::DefaultValue) if output == OutputFormat::Json => Some("json"),
+                    _ => ms.get_one::<String>("format").map(|s| s.as_str()),
+                };
+
+                let out = match format {
+                    Some("dot") => dependency_graph.format_dot(name),
+                    Some("json") => dependency_graph
+                        .format_json(name)
+                        .context("failed to format dependency graph")?,
+                    _ => dependency_graph.format_tree(name),
+                };
+
+                println!("{}", out.trim_end());
+
+                if ms.get_flag("validate") {
+                    // Only validate the requested extension, if any
+                    let m = Manifest {
+                        xs: m
+                            .xs
+                            .iter()
+                            .filter(|x| name.is_none_or(|name| x.name == name))
+                            .cloned()
+                            .collect(),
+                    };
+
+                    dependency_graph
+                        .validate_dependencies(&m)
+                        .context("dependency validation failed")?;
+
+                    if dependency_graph.has_cycles() {
+                        bail!(
+                            "dependency validation failed: circular dependencies detected\n{}",
+                            dependency_graph.format_cycles()
+                        );
+                    }
+
+                    eprintln!("Dependencies OK");
+                }
             }
 
             _ => unreachable!("invalid command"),
         },
 
-        Some((cmd, _)) => {
-            // Trim arguments for extension
-            let args: Vec<_> = args
-                .iter()
-                .skip(1)
-                .map(|arg| {
-                    arg.to_str()
-                        .expect("invalid command-line argument")
-                        .to_owned()
-                })
-                .collect();
+        Some(("completions", ms)) => {
+            let shell = ms.get_one::<String>("shell").context("missing shell")?;
+
+            let completer = current_exe().context("failed to get current executable")?;
+
+            Shells::builtins()
+                .completer(shell)
+                .context("unsupported shell")?
+                .write_registration(
+                    ENV_COMPLETE,                 // var
+                    SERVICE_NAME,                 // name
+                    SERVICE_NAME,                 // bin
+                    &completer.to_string_lossy(), // completer
+                    &mut stdout(),                // buf
+                )
+                .context("failed to write completion script")?;
+        }
+
+        Some(("docs", ms)) => {
+            let format = match ms.get_one::<String>("format").map(String::as_str) {
+                Some("markdown") => Format::Markdown,
+                _ => Format::Man,
+            };
+
+            let out = ms
+                .get_one::<PathBuf>("out")
+                .context("missing output directory")?;
+
+            let pages = docs::generate(c, format, out).context("failed to generate docs")?;
+
+            eprintln!("Wrote {} pages to {}", pages.len(), out.display());
+        }
+
+        Some((cmd, sms)) => {
+            // Resolve loading order (only the extensions required by the command)
+            let loading_order = dependency_graph
+                .resolve_loading_order_for(cmd)
+                .context("failed to resolve extension loading order")?;
+
+            // Extensions (load)
+            let (mut store, insts) = load_extensions(
+                &ngn,           // engine
+                &m,             // manifest
+                &loading_order, // loading order
+                stordir,        // storage directory
+                term,           // terminal
+                output,         // output format
+            )
+            .await?;
+
+            // Arguments (parsed by the host from the extension's spec)
+            let matches = match c.find_subcommand(cmd) {
+                Some(xc) => spec::matches(xc, sms),
+                None => unreachable!("invalid extension"),
+            };
 
             // Invoke extension
-            match insts.get(cmd) {
+            let exit_code = match insts.get(cmd) {
                 Some(inst) => {
-                    let _exit_code = inst //
+                    State::enter(store.as_context_mut(), cmd, "run-parsed")?;
+
+                    let out = inst
                         .icp_cli_cli()
-                        .call_run(&mut store, &args)
-                        .await?;
+                        .call_run_parsed(&mut store, &matches)
+                        .await;
+
+                    State::complete(store.as_context_mut(), out).map_err(|reason| {
+                        ExtensionError::Trapped {
+                            name: cmd.to_owned(),
+                            reason,
+                        }
+                    })?
                 }
                 None => unreachable!("invalid extension"),
-            }
+            };
+
+            return Ok(ExitCode::from(exit_code));
         }
 
         _ => unreachable!("invalid command"),
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{os::unix::fs::symlink, time::Duration};
+
+    use tempfile::TempDir;
+
+    use filesystem::Host as _;
+    use icp_core::sandbox::WORKSPACE_MANIFEST;
+
+    use super::*;
+
+    /// Creates a host confined to `ws`, next to an `outside` directory holding a `secret` file
+    fn setup() -> (TempDir, PathBuf, PathBuf, ExtensionHost) {
+        let tmp = TempDir::new().unwrap();
+        let base = tmp.path().canonicalize().unwrap();
+        let (ws, outside) = (base.join("ws"), base.join("outside"));
+
+        std::fs::create_dir_all(ws.join("dir")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(ws.join("file"), "inside").unwrap();
+        std::fs::write(outside.join("secret"), "secret").unwrap();
+
+        let host = ExtensionHost {
+            name: "test".to_owned(),
+            sandbox: Sandbox::new(&ws, [ws.clone()]),
+            permissions: Permissions::default(),
+            table: ResourceTable::new(),
+            random: Random::system(),
+            limits: Limits::default(),
+            storage: Storage::new(&base.join("storage"), "test"),
+            http: HttpClient::new(Allowlist::new([]), Duration::from_secs(1)),
+            wasi: None,
+            terminal: Terminal::new(false),
+            output: OutputFormat::default(),
+        };
+
+        (tmp, ws, outside, host)
+    }
+
+    #[tokio::test]
+    async fn test_remove_file_symlink() {
+        let (_tmp, ws, outside, mut host) = setup();
+
+        symlink(ws.join("file"), ws.join("inside")).unwrap();
+        symlink(outside.join("secret"), ws.join("outside")).unwrap();
+
+        // Removing the links leaves their targets untouched
+        host.remove_file("inside".into()).await.unwrap();
+        host.remove_file("outside".into()).await.unwrap();
+
+        assert!(ws.join("file").exists());
+        assert!(outside.join("secret").exists());
+        assert!(std::fs::symlink_metadata(ws.join("inside")).is_err());
+        assert!(std::fs::symlink_metadata(ws.join("outside")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remove_directory_symlink() {
+        let (_tmp, ws, outside, mut host) = setup();
+
+        symlink(ws.join("dir"), ws.join("inside")).unwrap();
+        symlink(&outside, ws.join("outside")).unwrap();
+
+        host.remove_directory("inside".into(), true).await.unwrap();
+        host.remove_directory("outside".into(), true).await.unwrap();
+
+        assert!(ws.join("dir").is_dir());
+        assert!(outside.join("secret").exists());
+
+        // Entries reached through a link are still confined to the sandbox
+        symlink(&outside, ws.join("link")).unwrap();
+
+        assert!(matches!(
+            host.remove_file("link/secret".into()).await,
+            Err(filesystem::Error::AccessDenied(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rename_symlink() {
+        let (_tmp, ws, outside, mut host) = setup();
+
+        symlink(outside.join("secret"), ws.join("outside")).unwrap();
+
+        // The link is moved, not its target
+        host.rename("outside".into(), "moved".into()).await.unwrap();
+
+        assert!(outside.join("secret").exists());
+        assert!(std::fs::symlink_metadata(ws.join("moved"))
+            .unwrap()
+            .file_type()
+            .is_symlink());
+    }
+
+    #[tokio::test]
+    async fn test_copy_symlink() {
+        let (_tmp, ws, outside, mut host) = setup();
+
+        symlink(ws.join("dir"), ws.join("inside")).unwrap();
+        symlink(outside.join("secret"), ws.join("outside")).unwrap();
+
+        // Copying onto a link replaces it instead of writing to its target
+        host.copy("file".into(), "outside".into()).await.unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(outside.join("secret")).unwrap(),
+            "secret"
+        );
+        assert_eq!(
+            std::fs::read_to_string(ws.join("outside")).unwrap(),
+            "inside"
+        );
+
+        // Sources are followed, but must stay within the sandbox
+        symlink(outside.join("secret"), ws.join("escape")).unwrap();
+
+        assert!(matches!(
+            host.copy("escape".into(), "copied".into()).await,
+            Err(filesystem::Error::AccessDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_preopens_from_subdirectory() {
+        let (_tmp, ws, outside, _) = setup();
+        std::fs::write(ws.join(WORKSPACE_MANIFEST), "").unwrap();
+
+        // Running from a subdirectory of the workspace
+        let cwd = ws.join("dir");
+        let root = find_workspace_root(&cwd);
+        let permissions = Permissions {
+            directories: vec![outside.clone(), outside.join("missing")],
+            ..Default::default()
+        };
+
+        let path = |p: &Path| p.to_string_lossy().into_owned();
+
+        assert_eq!(
+            preopens(&root, &cwd, &permissions),
+            vec![
+                (ws.clone(), path(&ws)),
+                (outside.clone(), path(&outside)),
+                (cwd.clone(), ".".to_owned()),
+            ]
+        );
+
+        // Relative paths resolve against the same directory as with the filesystem interface
+        let sandbox = Sandbox::new(&cwd, [root.clone()]);
+        assert_eq!(sandbox.resolve("file").unwrap(), cwd.join("file"));
+
+        assert!(wasi_ctx("test", &root, &cwd, &permissions, OutputFormat::Text).is_ok());
+    }
 }
@@ -0,0 +1,282 @@
+use std::{
+    env::{split_paths, var_os},
+    path::{Path, PathBuf},
+    process::Stdio as StdStdio,
+};
+
+use anyhow::{Context as _, Error};
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader},
+    process::{Child, ChildStdin, Command},
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+};
+
+use crate::icp::cli::command::{Output, SpawnOptions, Stdio};
+
+/// Host variables every command run for an extension gets, whether or not the extension may
+/// read them, so that the programs it runs in turn (e.g. the linker run by `cargo`) can be found
+pub const BASELINE_VARS: &[&str] = if cfg!(windows) {
+    &["PATH", "HOME", "SystemRoot", "TEMP", "TMP"]
+} else {
+    &["PATH", "HOME"]
+};
+
+/// Looks up the [`BASELINE_VARS`] that are set
+pub fn baseline_vars(lookup: impl Fn(&str) -> Option<String>) -> Vec<(String, String)> {
+    BASELINE_VARS
+        .iter()
+        .filter_map(|k| lookup(k).map(|v| (k.to_string(), v)))
+        .collect()
+}
+
+/// A process spawned on behalf of an extension
+pub struct Process {
+    /// The running process
+    child: Child,
+
+    /// Standard input, if piped and not yet closed
+    stdin: Option<ChildStdin>,
+
+    /// Output chunks read from the piped output streams
+    output: UnboundedReceiver<Output>,
+}
+
+impl From<Stdio> for StdStdio {
+    fn from(value: Stdio) -> Self {
+        match value {
+            Stdio::Piped => StdStdio::piped(),
+            Stdio::Inherit => StdStdio::inherit(),
+            Stdio::Null => StdStdio::null(),
+        }
+    }
+}
+
+impl Process {
+    /// Spawns a process, with its working directory already resolved by the caller
+    ///
+    /// The process does not inherit the host's environment: it only gets the given variables
+    /// (the baseline ones and those the extension may read), along with the ones set in the options.
+    pub fn spawn(
+        command: &str,
+        args: &[String],
+        cwd: Option<PathBuf>,
+        env: Vec<(String, String)>,
+        options: SpawnOptions,
+    ) -> Result<Self, Error> {
+        let mut cmd = Command::new(find_program(command));
+
+        cmd.args(args)
+            .env_clear()
+            .envs(env)
+            .envs(options.env)
+            .stdin(options.stdin)
+            .stdout(options.stdout)
+            .stderr(options.stderr)
+            .kill_on_drop(true);
+
+        if let Some(cwd) = cwd {
+            cmd.current_dir(cwd);
+        }
+
+        let mut child = cmd.spawn().context("failed to spawn command")?;
+
+        // Output (each piped stream is forwarded by its own task, so neither can block the other)
+        let (tx, output) = unbounded_channel();
+
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(forward(BufReader::new(stdout), tx.clone(), Output::Stdout));
+        }
+
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(forward(BufReader::new(stderr), tx, Output::Stderr));
+        }
+
+        Ok(Self {
+            stdin: child.stdin.take(),
+            child,
+            output,
+        })
+    }
+
+    /// Writes bytes to the standard input of the process
+    pub async fn write_stdin(&mut self, data: &[u8]) -> Result<(), Error> {
+        let stdin = self
+            .stdin
+            .as_mut()
+            .context("stdin is not piped or closed")?;
+
+        stdin
+            .write_all(data)
+            .await
+            .context("failed to write to stdin")?;
+        stdin.flush().await.context("failed to flush stdin")?;
+
+        Ok(())
+    }
+
+    /// Closes the standard input of the process
+    pub fn close_stdin(&mut self) {
+        self.stdin = None;
+    }
+
+    /// Waits for the next chunk of output, or `None` once all piped output streams are closed
+    pub async fn read(&mut self) -> Option<Output> {
+        self.output.recv().await
+    }
+
+    /// Waits for the process to exit and returns its exit code
+    pub async fn wait(&mut self) -> Result<u32, Error> {
+        self.close_stdin();
+
+        let status = self
+            .child
+            .wait()
+            .await
+            .context("failed to wait for process")?;
+
+        Ok(status.code().unwrap_or(1) as u32)
+    }
+}
+
+/// Locates a command in the host's `PATH`
+///
+/// Commands given as paths are returned as they are, as are commands that are not found
+/// (spawning them then fails with a not-found error).
+pub fn find_program(command: &str) -> PathBuf {
+    let p = Path::new(command);
+
+    if p.components().count() > 1 {
+        return p.to_owned();
+    }
+
+    let suffixes: &[&str] = if cfg!(windows) { &["", ".exe"] } else { &[""] };
+
+    var_os("PATH")
+        .and_then(|paths| {
+            split_paths(&paths)
+                .flat_map(|dir| {
+                    suffixes
+                        .iter()
+                        .map(move |s| dir.join(format!("{command}{s}")))
+                })
+                .find(|p| p.is_file())
+        })
+        .unwrap_or_else(|| p.to_owned())
+}
+
+/// Forwards a stream line by line until it is closed
+async fn forward(
+    mut r: impl AsyncBufRead + Unpin,
+    tx: UnboundedSender<Output>,
+    f: fn(Vec<u8>) -> Output,
+) {
+    loop {
+        let mut buf = vec![];
+
+        match r.read_until(b'\n', &mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                if tx.send(f(buf)).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_program() {
+        assert!(find_program("sh").is_absolute());
+        assert_eq!(find_program("./sh"), PathBuf::from("./sh"));
+        assert_eq!(find_program("no-such-cmd"), PathBuf::from("no-such-cmd"));
+    }
+
+    #[tokio::test]
+    async fn test_spawn_env() -> Result<(), Error> {
+        let options = SpawnOptions {
+            cwd: None,
+            env: vec![("REQUESTED".to_string(), "2".to_string())],
+            stdin: Stdio::Null,
+            stdout: Stdio::Piped,
+            stderr: Stdio::Null,
+        };
+
+        let mut p = Process::spawn(
+            "env",                                          // command
+            &[],                                            // args
+            None,                                           // cwd
+            vec![("ALLOWED".to_string(), "1".to_string())], // env
+            options,                                        // options
+        )?;
+
+        let mut out = vec![];
+        while let Some(Output::Stdout(bs)) = p.read().await {
+            out.extend(bs);
+        }
+
+        assert_eq!(p.wait().await?, 0);
+
+        // Host variables (e.g. PATH) are not inherited
+        let mut vars: Vec<_> = String::from_utf8(out)?.lines().map(str::to_owned).collect();
+        vars.sort();
+
+        assert_eq!(vars, vec!["ALLOWED=1", "REQUESTED=2"]);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_spawn_baseline_path() -> Result<(), Error> {
+        use std::os::unix::fs::PermissionsExt;
+
+        // A helper that the spawned shell can only find through PATH
+        let tmp = tempfile::TempDir::new()?;
+        let helper = tmp.path().join("icp-test-helper");
+
+        std::fs::write(&helper, "#!/bin/sh\necho found\n")?;
+        std::fs::set_permissions(&helper, std::fs::Permissions::from_mode(0o755))?;
+
+        let env = baseline_vars(|k| match k {
+            "PATH" => Some(format!("{}:/usr/bin:/bin", tmp.path().display())),
+            _ => std::env::var(k).ok(),
+        });
+
+        let options = SpawnOptions {
+            cwd: None,
+            env: vec![],
+            stdin: Stdio::Null,
+            stdout: Stdio::Piped,
+            stderr: Stdio::Null,
+        };
+
+        let mut p = Process::spawn(
+            "sh",                                               // command
+            &["-c".to_string(), "icp-test-helper".to_string()], // args
+            None,                                               // cwd
+            env,                                                // env
+            options,                                            // options
+        )?;
+
+        let mut out = vec![];
+        while let Some(Output::Stdout(bs)) = p.read().await {
+            out.extend(bs);
+        }
+
+        assert_eq!(p.wait().await?, 0);
+        assert_eq!(String::from_utf8(out)?, "found\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_baseline_vars() {
+        let vars = baseline_vars(|k| (k == "PATH").then(|| "/bin".to_string()));
+
+        assert_eq!(vars, vec![("PATH".to_string(), "/bin".to_string())]);
+    }
+}
@@ -0,0 +1,438 @@
+use std::{fs::read_to_string, path::PathBuf, sync::Arc};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use icp_distribution::{resolve_url, DistributionError, RegistryExtension, RegistryIndex};
+use reqwest::get;
+use semver::{Version, VersionReq};
+use url::Url;
+
+use icp_core::{
+    interface::{incompatible_host_version, HOST_INTERFACE_VERSION},
+    manifest::Permissions,
+};
+
+use crate::extension::{AddExtension, AddExtensionError, Release};
+
+/// Returns the local path designated by a `file://` URL or a plain path, or `None` for remote URLs
+///
+/// `file://` URLs are decoded (e.g. `%20`), and fail to convert if they name a remote host
+/// or are not absolute.
+pub fn local_path(url: &str) -> Result<Option<PathBuf>, anyhow::Error> {
+    if !url.contains("://") {
+        return Ok(Some(url.into()));
+    }
+
+    let u = Url::parse(url).context(format!("invalid URL {url}"))?;
+
+    if u.scheme() != "file" {
+        return Ok(None);
+    }
+
+    match u.to_file_path() {
+        Ok(p) => Ok(Some(p)),
+        Err(()) => Err(anyhow::anyhow!("{url} does not designate a local path")),
+    }
+}
+
+/// Returns the versions of a registry extension built for the `icp:cli` interfaces of this host,
+/// leaving out the ones built for an incompatible version (versions listing no interfaces are kept)
+pub fn supported(x: &RegistryExtension) -> RegistryExtension {
+    RegistryExtension {
+        versions: x
+            .versions
+            .iter()
+            .filter(|v| {
+                let names = v.imports.iter().chain(&v.exports).map(String::as_str);
+                incompatible_host_version(names).is_none()
+            })
+            .cloned()
+            .collect(),
+        ..x.clone()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FetchIndexError {
+    #[error("invalid registry index {url}: {err}")]
+    InvalidIndex { url: String, err: DistributionError },
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+#[async_trait]
+pub trait FetchIndex: Sync + Send {
+    async fn fetch(&self, url: &str) -> Result<RegistryIndex, FetchIndexError>;
+}
+
+/// Fetches registry indexes over HTTP, or from the local file system for `file://` URLs and paths
+pub struct IndexFetcher;
+
+#[async_trait]
+impl FetchIndex for IndexFetcher {
+    async fn fetch(&self, url: &str) -> Result<RegistryIndex, FetchIndexError> {
+        let s = match local_path(url)? {
+            Some(p) => {
+                read_to_string(&p).context(format!("failed to read registry index {url}"))?
+            }
+
+            None => get(url)
+                .await
+                .and_then(|r| r.error_for_status())
+                .context(format!("failed to download registry index {url}"))?
+                .text()
+                .await
+                .context("failed to read body")?,
+        };
+
+        RegistryIndex::parse(&s).map_err(|err| FetchIndexError::InvalidIndex {
+            url: url.to_owned(),
+            err,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum InstallExtensionError {
+    #[error("extension {0} not found in registry")]
+    NotFound(String),
+
+    #[error("no version of extension {name} matches {req}")]
+    NoMatchingVersion { name: String, req: String },
+
+    #[error("extension {name} {version} was built for icp:cli@{built}, which is not supported (icp supports icp:cli@{HOST_INTERFACE_VERSION})")]
+    IncompatibleHost {
+        name: String,
+        version: Version,
+        built: String,
+    },
+
+    #[error(transparent)]
+    FetchIndex(#[from] FetchIndexError),
+
+    #[error(transparent)]
+    AddExtension(#[from] AddExtensionError),
+
+    #[error(transparent)]
+    UnexpectedError(#[from] anyhow::Error),
+}
+
+#[async_trait]
+pub trait InstallExtension: Sync + Send {
+    /// Installs the newest version of an extension matching a requirement, verified against
+    /// the checksum listed in the registry index
+    ///
+    /// # Returns
+    ///
+    /// The installed version
+    async fn install(
+        &self,
+        registry: &str,
+        name: &str,
+        req: Option<&VersionReq>,
+        force: bool,
+        permissions: Permissions,
+    ) -> Result<Version, InstallExtensionError>;
+}
+
+pub struct ExtensionInstaller {
+    // Registry index fetcher
+    fetcher: Arc<dyn FetchIndex>,
+
+    // Extension adder (which verifies the checksum)
+    add: Arc<dyn AddExtension>,
+}
+
+impl ExtensionInstaller {
+    pub fn new(fetcher: Arc<dyn FetchIndex>, add: Arc<dyn AddExtension>) -> Self {
+        Self { fetcher, add }
+    }
+}
+
+#[async_trait]
+impl InstallExtension for ExtensionInstaller {
+    async fn install(
+        &self,
+        registry: &str,
+        name: &str,
+        req: Option<&VersionReq>,
+        force: bool,
+        permissions: Permissions,
+    ) -> Result<Version, InstallExtensionError> {
+        let idx = self.fetcher.fetch(registry).await?;
+
+        let x = idx
+            .get(name)
+            .ok_or(InstallExtensionError::NotFound(name.to_owned()))?;
+
+        let v = match (supported(x).resolve(req).cloned(), x.resolve(req)) {
+            (Some(v), _) => v,
+
+            // Only versions built for another host interface match
+            (None, Some(v)) => {
+                let names = v.imports.iter().chain(&v.exports).map(String::as_str);
+
+                return Err(InstallExtensionError::IncompatibleHost {
+                    name: name.to_owned(),
+                    version: v.version.clone(),
+                    built: incompatible_host_version(names).unwrap_or_default(),
+                });
+            }
+
+            (None, None) => {
+                return Err(InstallExtensionError::NoMatchingVersion {
+                    name: name.to_owned(),
+                    req: req.map_or("a stable release".to_owned(), |r| r.to_string()),
+                })
+            }
+        };
+
+        // Components listed in a local index are read from disk
+        let url = resolve_url(registry, &v.url);
+        let p = match local_path(&url)? {
+            Some(p) => p.to_string_lossy().into_owned(),
+            None => url,
+        };
+
+        let release = Release {
+            registry: registry.to_owned(),
+            version: v.version.to_string(),
+        };
+
+        self.add
+            .add(
+                name,            // name
+                &p,              // uri
+                Some(&v.sha256), // checksum
+                force,           // force
+                permissions,     // permissions
+                Some(release),   // release
+            )
+            .await?;
+
+        Ok(v.version)
+    }
+}
+
+/// Test doubles shared by the tests of the registry and of extension management
+#[cfg(test)]
+pub mod testing {
+    use super::*;
+
+    /// Serves a fixed registry index
+    pub struct FakeIndex(pub RegistryIndex);
+
+    #[async_trait]
+    impl FetchIndex for FakeIndex {
+        async fn fetch(&self, _url: &str) -> Result<RegistryIndex, FetchIndexError> {
+            Ok(self.0.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::write, sync::Mutex};
+
+    use icp_distribution::RegistryVersion;
+    use sha2::{Digest, Sha256};
+    use tempfile::TempDir;
+
+    use super::{testing::FakeIndex, *};
+
+    const SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    /// Records the components it is asked to add
+    #[derive(Default)]
+    struct FakeAdder(Mutex<Vec<(String, String, Option<String>)>>);
+
+    #[async_trait]
+    impl AddExtension for FakeAdder {
+        async fn add(
+            &self,
+            name: &str,
+            p: &str,
+            checksum: Option<&str>,
+            _force: bool,
+            _permissions: Permissions,
+            _release: Option<Release>,
+        ) -> Result<(), AddExtensionError> {
+            self.0.lock().unwrap().push((
+                name.to_owned(),
+                p.to_owned(),
+                checksum.map(str::to_owned),
+            ));
+
+            Ok(())
+        }
+    }
+
+    /// Publishes a version of the `build` extension importing `icp:cli/misc` at the given version
+    fn publish(idx: &mut RegistryIndex, v: &str, host: Option<&str>) {
+        idx.publish(
+            "build",
+            None,
+            RegistryVersion {
+                version: Version::parse(v).unwrap(),
+                url: format!("build-{v}.component.wasm"),
+                sha256: SHA256.to_owned(),
+                imports: host
+                    .map(|h| format!("icp:cli/misc@{h}"))
+                    .into_iter()
+                    .collect(),
+                exports: vec![],
+            },
+        );
+    }
+
+    fn installer(idx: RegistryIndex) -> (ExtensionInstaller, Arc<FakeAdder>) {
+        let add = Arc::new(FakeAdder::default());
+
+        (
+            ExtensionInstaller::new(Arc::new(FakeIndex(idx)), add.clone()),
+            add,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_install_skips_incompatible_host() {
+        let mut idx = RegistryIndex::default();
+        publish(&mut idx, "1.0.0", Some(HOST_INTERFACE_VERSION));
+        publish(&mut idx, "1.1.0", None);
+        publish(&mut idx, "2.0.0", Some("0.4.0"));
+
+        let (inst, add) = installer(idx);
+
+        // The newest version built for this host (or listing no interfaces) is installed
+        let v = inst
+            .install(
+                "/srv/registry/index.json", // registry
+                "build",                    // name
+                None,                       // req
+                false,                      // force
+                Permissions::default(),     // permissions
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(v, Version::parse("1.1.0").unwrap());
+        assert_eq!(
+            add.0.lock().unwrap()[0],
+            (
+                "build".to_owned(),
+                "/srv/registry/build-1.1.0.component.wasm".to_owned(),
+                Some(SHA256.to_owned())
+            )
+        );
+
+        // Requiring a version built for another host fails
+        let req = VersionReq::parse("=2.0.0").unwrap();
+
+        let err = inst
+            .install(
+                "/srv/registry/index.json", // registry
+                "build",                    // name
+                Some(&req),                 // req
+                false,                      // force
+                Permissions::default(),     // permissions
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            InstallExtensionError::IncompatibleHost { built, .. } if built == "0.4"
+        ));
+    }
+
+    #[test]
+    fn test_local_path() {
+        let path = |url| local_path(url).unwrap();
+
+        assert_eq!(path("/srv/registry"), Some("/srv/registry".into()));
+        assert_eq!(
+            path("registry/index.json"),
+            Some("registry/index.json".into())
+        );
+        assert_eq!(path("https://example.com/index.json"), None);
+
+        #[cfg(unix)]
+        {
+            assert_eq!(path("file:///srv/registry"), Some("/srv/registry".into()));
+            assert_eq!(path("file://localhost/srv/a%20b"), Some("/srv/a b".into()));
+
+            // Hosts other than the local one don't designate a local path
+            assert!(local_path("file://host/srv/registry").is_err());
+        }
+
+        #[cfg(windows)]
+        {
+            assert_eq!(path("file:///C:/registry"), Some("C:\\registry".into()));
+            assert_eq!(path("file://C:/registry"), Some("C:\\registry".into()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_install_file_index() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("my registry");
+        std::fs::create_dir(&dir).unwrap();
+
+        // A component listed relative to the index
+        let component = b"component".as_slice();
+        write(dir.join("build-1.0.0.component.wasm"), component).unwrap();
+
+        let mut idx = RegistryIndex::default();
+        idx.publish(
+            "build",
+            None,
+            RegistryVersion {
+                version: Version::new(1, 0, 0),
+                url: "build-1.0.0.component.wasm".to_owned(),
+                sha256: format!("{:x}", Sha256::digest(component)),
+                imports: vec![],
+                exports: vec![],
+            },
+        );
+
+        write(dir.join("index.json"), serde_json::to_string(&idx).unwrap()).unwrap();
+
+        let registry = Url::from_file_path(dir.join("index.json"))
+            .unwrap()
+            .to_string();
+
+        let add = Arc::new(FakeAdder::default());
+        let inst = ExtensionInstaller::new(Arc::new(IndexFetcher), add.clone());
+
+        let v = inst
+            .install(
+                &registry,              // registry
+                "build",                // name
+                None,                   // req
+                false,                  // force
+                Permissions::default(), // permissions
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(v, Version::new(1, 0, 0));
+
+        // The component is read from the registry directory, and matches the listed checksum
+        let (name, p, checksum) = add.0.lock().unwrap()[0].clone();
+
+        assert_eq!(name, "build");
+        assert_eq!(PathBuf::from(&p), dir.join("build-1.0.0.component.wasm"));
+        assert_eq!(
+            checksum,
+            Some(format!("{:x}", Sha256::digest(std::fs::read(&p).unwrap())))
+        );
+
+        // Unknown extensions
+        assert!(matches!(
+            inst.install(&registry, "deploy", None, false, Permissions::default())
+                .await,
+            Err(InstallExtensionError::NotFound(_))
+        ));
+    }
+}
@@ -1,98 +1,397 @@
-use clap::{Arg, Command};
-use serde::Deserialize;
+use clap::{
+    builder::{BoolishValueParser, CommandExt, PossibleValuesParser},
+    Arg, ArgAction, ArgMatches, Command,
+};
+use icp_core::{
+    manifest::Permissions,
+    spec::{ArgSpec, CommandSpec, ExampleSpec},
+};
 
-#[derive(Debug, Deserialize)]
-pub struct CommandSpec {
-    /// Name of the command
-    pub name: String,
+use crate::exports::icp::cli::cli::{Arg as MatchedArg, ArgValue, CommandMatches};
 
-    /// Help text for the command
-    pub help: Option<String>,
+/// Usage examples of a command, kept on it for the reference docs
+#[derive(Debug, Clone)]
+pub struct Examples(pub Vec<ExampleSpec>);
 
-    /// Version of the command
-    pub version: Option<String>,
+impl CommandExt for Examples {}
 
-    /// Argument specification
-    #[serde(default)]
-    pub args: Vec<ArgSpec>,
+/// Converts a command specification into a clap `Command`
+///
+/// Environment variables are only used as fallbacks for arguments if the extension is
+/// allowed to read them.
+pub fn command(value: CommandSpec, permissions: &Permissions) -> Command {
+    // Command
+    let mut c = Command::new(value.name);
 
-    /// Subcommands specification
-    #[serde(default)]
-    pub subcommands: Vec<CommandSpec>,
+    // Help
+    if let Some(help) = value.help {
+        c = c.about(help);
+    }
+
+    // Long help
+    if let Some(long_help) = value.long_help {
+        c = c.long_about(long_help);
+    }
+
+    // Examples
+    if !value.examples.is_empty() {
+        c = c
+            .after_long_help(examples(&value.examples))
+            .add(Examples(value.examples));
+    }
+
+    // Version
+    if let Some(ver) = value.version {
+        c = c.version(ver);
+    }
+
+    // Aliases
+    c = c.visible_aliases(value.aliases);
+
+    // Hidden
+    c = c.hide(value.hidden);
+
+    // Args
+    let c = value
+        .args
+        .into_iter()
+        .fold(c, |acc, cur| acc.arg(arg(cur, permissions)));
+
+    // Subcommands
+    let c = value
+        .subcommands
+        .into_iter()
+        .fold(c, |acc, cur| acc.subcommand(command(cur, permissions)));
+
+    #[allow(clippy::let_and_return)]
+    c
 }
 
-impl From<CommandSpec> for Command {
-    fn from(value: CommandSpec) -> Self {
-        // Command
-        let mut c = Command::new(value.name);
+/// Converts an argument specification into a clap `Arg`
+fn arg(value: ArgSpec, permissions: &Permissions) -> Arg {
+    let positional = value.is_positional();
 
-        // Help
-        if let Some(help) = value.help {
-            c = c.about(help);
-        }
+    // Arg
+    let mut c = Arg::new(value.name);
 
-        // Version
-        if let Some(ver) = value.version {
-            c = c.version(ver);
-        }
+    // Help
+    if let Some(help) = value.help {
+        c = c.help(help);
+    }
+
+    // Long help
+    if let Some(long_help) = value.long_help {
+        c = c.long_help(long_help);
+    }
 
-        // Args
-        let c = value.args.into_iter().fold(c, |acc, cur| acc.arg(cur));
+    // Short
+    if let Some(short) = value.short {
+        c = c.short(short);
+    }
 
-        // Subcommands
-        let c = value
-            .subcommands
-            .into_iter()
-            .fold(c, |acc, cur| acc.subcommand(cur));
+    // Long
+    if let Some(long) = value.long {
+        c = c.long(long);
+    }
 
-        #[allow(clippy::let_and_return)]
-        c
+    // Aliases
+    c = c.visible_aliases(value.aliases);
+
+    // Required
+    if let Some(required) = value.required {
+        c = c.required(required);
     }
+
+    // Action
+    c = match (value.flag, value.multiple) {
+        (true, _) => c.action(ArgAction::SetTrue),
+        (false, true) if positional => c.action(ArgAction::Append).num_args(1..),
+        (false, true) => c.action(ArgAction::Append),
+        (false, false) => c.action(ArgAction::Set),
+    };
+
+    // Default
+    if let Some(default) = value.default {
+        c = c.default_value(default);
+    }
+
+    // Possible values
+    if !value.possible_values.is_empty() {
+        c = c.value_parser(PossibleValuesParser::new(value.possible_values));
+    }
+
+    // Env
+    if let Some(env) = value.env.filter(|v| permissions.allows_variable(v)) {
+        c = c.env(env);
+
+        // Accept the usual spellings of booleans (e.g. `1` or `yes`) from the environment
+        if value.flag {
+            c = c.value_parser(BoolishValueParser::new());
+        }
+    }
+
+    // Hidden
+    c = c.hide(value.hidden);
+
+    c
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ArgSpec {
-    /// Name of the argument
-    pub name: String,
+/// Renders usage examples for `--help`
+pub fn examples(examples: &[ExampleSpec]) -> String {
+    let lines = examples.iter().map(|e| match &e.help {
+        Some(help) => format!("  # {help}\n  $ {}", e.command),
+        None => format!("  $ {}", e.command),
+    });
 
-    /// Help text for the argument
-    pub help: Option<String>,
+    format!("Examples:\n{}", lines.collect::<Vec<_>>().join("\n\n"))
+}
+
+/// Collects the arguments of an extension command parsed by the host, for each command from
+/// the extension's top-level command down to the invoked subcommand
+///
+/// # Arguments
+///
+/// * `cmd` - The extension's command
+/// * `ms` - Matches of the extension's command
+pub fn matches(cmd: &Command, ms: &ArgMatches) -> Vec<CommandMatches> {
+    let mut out = vec![];
+    let mut next = Some((cmd, ms));
+
+    while let Some((cmd, ms)) = next {
+        let mut args = vec![];
+
+        for a in cmd.get_arguments() {
+            let id = a.get_id().as_str();
+
+            // Arguments neither given nor defaulted
+            if ms.value_source(id).is_none() {
+                continue;
+            }
 
-    /// Short name for the argument
-    pub short: Option<char>,
+            let value = match a.get_action() {
+                ArgAction::SetTrue => ArgValue::Flag(ms.get_flag(id)),
+                ArgAction::Set => match ms.get_raw(id).into_iter().flatten().next() {
+                    Some(v) => ArgValue::One(v.to_string_lossy().into_owned()),
+                    None => continue,
+                },
+                ArgAction::Append => ArgValue::Many(
+                    ms.get_raw(id)
+                        .into_iter()
+                        .flatten()
+                        .map(|v| v.to_string_lossy().into_owned())
+                        .collect(),
+                ),
 
-    /// Long name for the argument
-    pub long: Option<String>,
+                // Generated help and version flags
+                _ => continue,
+            };
+
+            args.push(MatchedArg {
+                name: id.to_owned(),
+                value,
+            });
+        }
+
+        out.push(CommandMatches {
+            name: cmd.get_name().to_owned(),
+            args,
+        });
+
+        next = ms
+            .subcommand()
+            .and_then(|(name, ms)| Some((cmd.find_subcommand(name)?, ms)));
+    }
 
-    /// Whether argument is required
-    pub required: Option<bool>,
+    out
 }
 
-impl From<ArgSpec> for Arg {
-    fn from(value: ArgSpec) -> Self {
-        // Arg
-        let mut c = Arg::new(value.name);
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::{remove_var, set_var, var_os},
+        ffi::OsString,
+        sync::{Mutex, MutexGuard},
+    };
 
-        // Help
-        if let Some(help) = value.help {
-            c = c.help(help);
+    use serde_json::json;
+
+    use super::*;
+
+    /// Serializes the tests that modify the environment
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Sets environment variables for the duration of a test, restoring them when dropped.
+    /// clap reads the variables of an argument when it is built, so they cannot be injected.
+    struct EnvGuard {
+        saved: Vec<(&'static str, Option<OsString>)>,
+        _lock: MutexGuard<'static, ()>,
+    }
+
+    impl EnvGuard {
+        fn new() -> Self {
+            Self {
+                saved: vec![],
+                _lock: ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner()),
+            }
         }
 
-        // Short
-        if let Some(short) = value.short {
-            c = c.short(short);
+        fn set(&mut self, name: &'static str, value: &str) {
+            if !self.saved.iter().any(|(n, _)| *n == name) {
+                self.saved.push((name, var_os(name)));
+            }
+
+            set_var(name, value);
         }
+    }
 
-        // Long
-        if let Some(long) = value.long {
-            c = c.long(long);
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            for (name, value) in self.saved.drain(..) {
+                match value {
+                    Some(v) => set_var(name, v),
+                    None => remove_var(name),
+                }
+            }
         }
+    }
 
-        // Required
-        if let Some(required) = value.required {
-            c = c.required(required);
+    /// Builds the `build` command of a test extension, with a `deploy` subcommand
+    fn spec() -> CommandSpec {
+        serde_json::from_value(json!({
+            "name": "build",
+            "help": "Build canisters",
+            "args": [
+                { "name": "verbose", "long": "verbose", "flag": true, "env": "ICP_SPEC_TEST_VERBOSE" },
+                { "name": "mode", "long": "mode", "default": "debug", "env": "ICP_SPEC_TEST_MODE" },
+                { "name": "feature", "long": "feature", "multiple": true },
+                { "name": "canisters", "multiple": true }
+            ],
+            "subcommands": [{
+                "name": "deploy",
+                "help": "Deploy canisters",
+                "args": [{ "name": "network", "long": "network", "required": true }]
+            }]
+        }))
+        .unwrap()
+    }
+
+    /// Permissions allowing to read the test variables
+    fn allowed() -> Permissions {
+        Permissions {
+            variables: vec!["ICP_SPEC_TEST_*".into()],
+            ..Default::default()
         }
+    }
+
+    fn parse(permissions: &Permissions, args: &[&str]) -> Vec<CommandMatches> {
+        let cmd = command(spec(), permissions);
+        let ms = cmd.clone().try_get_matches_from(args).unwrap();
+
+        matches(&cmd, &ms)
+    }
+
+    fn value<'a>(ms: &'a CommandMatches, name: &str) -> Option<&'a ArgValue> {
+        ms.args.iter().find(|a| a.name == name).map(|a| &a.value)
+    }
+
+    #[test]
+    fn test_command() {
+        let cmd = command(spec(), &Permissions::default());
+
+        assert_eq!(cmd.get_name(), "build");
+        assert!(cmd.find_subcommand("deploy").is_some());
+
+        let ids: Vec<_> = cmd.get_arguments().map(|a| a.get_id().as_str()).collect();
+        assert_eq!(ids, ["verbose", "mode", "feature", "canisters"]);
+
+        // Missing required arguments are rejected
+        assert!(cmd.try_get_matches_from(["build", "deploy"]).is_err());
+    }
+
+    #[test]
+    fn test_matches() {
+        let out = parse(
+            &Permissions::default(),
+            &["build", "--verbose", "deploy", "--network", "local"],
+        );
+
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].name, "build");
+        assert_eq!(out[1].name, "deploy");
+
+        assert!(matches!(
+            value(&out[0], "verbose"),
+            Some(ArgValue::Flag(true))
+        ));
+        assert!(matches!(value(&out[0], "mode"), Some(ArgValue::One(v)) if v == "debug"));
+        assert!(matches!(value(&out[1], "network"), Some(ArgValue::One(v)) if v == "local"));
+    }
+
+    #[test]
+    fn test_matches_repeated() {
+        let out = parse(
+            &Permissions::default(),
+            &["build", "--feature", "a", "x", "--feature", "b", "y"],
+        );
+
+        assert_eq!(out.len(), 1);
+
+        // Repeated options and positionals are collected in order
+        assert!(matches!(value(&out[0], "feature"), Some(ArgValue::Many(vs)) if vs == &["a", "b"]));
+        assert!(
+            matches!(value(&out[0], "canisters"), Some(ArgValue::Many(vs)) if vs == &["x", "y"])
+        );
+    }
+
+    #[test]
+    fn test_matches_omitted() {
+        let out = parse(&Permissions::default(), &["build"]);
+
+        assert_eq!(out.len(), 1);
+
+        // Unset flags are reported as false, arguments neither given nor defaulted are left out
+        assert!(matches!(
+            value(&out[0], "verbose"),
+            Some(ArgValue::Flag(false))
+        ));
+        assert!(value(&out[0], "feature").is_none());
+        assert!(value(&out[0], "canisters").is_none());
+    }
+
+    #[test]
+    fn test_matches_env() {
+        let mut env = EnvGuard::new();
+        env.set("ICP_SPEC_TEST_VERBOSE", "yes");
+        env.set("ICP_SPEC_TEST_MODE", "release");
+
+        // Variables are only used when the extension may read them
+        let out = parse(&Permissions::default(), &["build"]);
+
+        assert!(matches!(
+            value(&out[0], "verbose"),
+            Some(ArgValue::Flag(false))
+        ));
+        assert!(matches!(value(&out[0], "mode"), Some(ArgValue::One(v)) if v == "debug"));
+
+        let out = parse(&allowed(), &["build"]);
+
+        // Boolish spellings are accepted for flags
+        assert!(matches!(
+            value(&out[0], "verbose"),
+            Some(ArgValue::Flag(true))
+        ));
+        assert!(matches!(value(&out[0], "mode"), Some(ArgValue::One(v)) if v == "release"));
+
+        env.set("ICP_SPEC_TEST_VERBOSE", "0");
+
+        let out = parse(&allowed(), &["build", "--mode", "test"]);
+
+        assert!(matches!(
+            value(&out[0], "verbose"),
+            Some(ArgValue::Flag(false))
+        ));
 
-        c
+        // Arguments given on the command line take precedence
+        assert!(matches!(value(&out[0], "mode"), Some(ArgValue::One(v)) if v == "test"));
     }
 }
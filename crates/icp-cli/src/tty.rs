@@ -0,0 +1,242 @@
+use std::{
+    env::var_os,
+    io::{stderr, stdin, stdout, IsTerminal},
+    time::Duration,
+};
+
+use dialoguer::{
+    theme::{ColorfulTheme, SimpleTheme, Theme},
+    Confirm, Input, Password, Select,
+};
+use indicatif::{ProgressBar, ProgressStyle};
+use once_cell::sync::Lazy;
+
+use crate::icp::cli::terminal::Stream;
+
+/// Interval at which spinners are redrawn
+const SPINNER_TICK: Duration = Duration::from_millis(100);
+
+#[derive(Debug, thiserror::Error)]
+pub enum PromptError {
+    #[error("cannot prompt in non-interactive mode and no default answer is available")]
+    NonInteractive,
+
+    #[error("invalid prompt: {0}")]
+    Invalid(String),
+
+    #[error(transparent)]
+    Io(#[from] dialoguer::Error),
+}
+
+/// Prompts the user on the terminal (on stderr, so that stdout can be redirected).
+///
+/// Prompts are only shown when the terminal is interactive, otherwise they return their
+/// default answer, or fail with [`PromptError::NonInteractive`] if they have none.
+#[derive(Debug, Clone, Copy)]
+pub struct Terminal {
+    interactive: bool,
+}
+
+impl Terminal {
+    /// Creates a terminal, which is interactive when stdin and stderr are terminals,
+    /// unless `assume_defaults` is set (e.g. with `--yes`) or running in CI
+    pub fn new(assume_defaults: bool) -> Self {
+        let ci = var_os("CI").is_some_and(|v| !v.is_empty() && v != "0" && v != "false");
+
+        Self {
+            interactive: interactive(
+                assume_defaults,                                 // assume_defaults
+                stdin().is_terminal() && stderr().is_terminal(), // is_terminal
+                ci,                                              // ci
+            ),
+        }
+    }
+
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    pub fn prompt(&self, message: &str, default: Option<String>) -> Result<String, PromptError> {
+        if !self.interactive {
+            return default.ok_or(PromptError::NonInteractive);
+        }
+
+        let mut p = Input::<String>::with_theme(theme()).with_prompt(message);
+
+        if let Some(default) = default {
+            p = p.default(default);
+        }
+
+        Ok(p.interact_text()?)
+    }
+
+    pub fn confirm(&self, message: &str, default: Option<bool>) -> Result<bool, PromptError> {
+        if !self.interactive {
+            return default.ok_or(PromptError::NonInteractive);
+        }
+
+        let mut p = Confirm::with_theme(theme()).with_prompt(message);
+
+        if let Some(default) = default {
+            p = p.default(default);
+        }
+
+        Ok(p.interact()?)
+    }
+
+    /// Asks to choose one of several items, returning the index of the chosen one
+    pub fn select(
+        &self,
+        message: &str,
+        items: &[String],
+        default: Option<usize>,
+    ) -> Result<usize, PromptError> {
+        if items.is_empty() {
+            return Err(PromptError::Invalid("no items to select from".into()));
+        }
+
+        if default.is_some_and(|d| d >= items.len()) {
+            return Err(PromptError::Invalid("default item out of range".into()));
+        }
+
+        if !self.interactive {
+            return default.ok_or(PromptError::NonInteractive);
+        }
+
+        Ok(Select::with_theme(theme())
+            .with_prompt(message)
+            .items(items)
+            .default(default.unwrap_or_default())
+            .interact()?)
+    }
+
+    /// Asks for a secret, which has no default answer
+    pub fn password(&self, message: &str) -> Result<String, PromptError> {
+        if !self.interactive {
+            return Err(PromptError::NonInteractive);
+        }
+
+        Ok(Password::with_theme(theme())
+            .with_prompt(message)
+            .interact()?)
+    }
+
+    /// Starts a progress bar drawn on stderr (hidden when it is not a terminal),
+    /// or a spinner if the total is unknown
+    pub fn progress(&self, message: String, total: Option<u64>) -> ProgressBar {
+        let pb = match total {
+            Some(total) => ProgressBar::new(total).with_style(
+                ProgressStyle::with_template("{msg} [{bar:40}] {pos}/{len}")
+                    .expect("invalid progress template")
+                    .progress_chars("=> "),
+            ),
+            None => {
+                let pb = ProgressBar::new_spinner();
+                pb.enable_steady_tick(SPINNER_TICK);
+                pb
+            }
+        };
+
+        pb.with_message(message)
+    }
+}
+
+/// Decides whether prompts are shown: only on a terminal, outside of CI, and when defaults
+/// are not assumed
+fn interactive(assume_defaults: bool, is_terminal: bool, ci: bool) -> bool {
+    !assume_defaults && is_terminal && !ci
+}
+
+/// Checks whether a standard stream is a terminal
+pub fn is_terminal(s: Stream) -> bool {
+    match s {
+        Stream::Stdin => stdin().is_terminal(),
+        Stream::Stdout => stdout().is_terminal(),
+        Stream::Stderr => stderr().is_terminal(),
+    }
+}
+
+/// Checks whether colours should be used on a standard stream, following the
+/// `NO_COLOR` (https://no-color.org) and `CLICOLOR_FORCE` conventions
+pub fn supports_color(s: Stream) -> bool {
+    if var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return false;
+    }
+
+    var_os("CLICOLOR_FORCE").is_some_and(|v| !v.is_empty() && v != "0") || is_terminal(s)
+}
+
+fn theme() -> &'static dyn Theme {
+    static COLORFUL: Lazy<ColorfulTheme> = Lazy::new(ColorfulTheme::default);
+
+    match supports_color(Stream::Stderr) {
+        true => &*COLORFUL,
+        false => &SimpleTheme,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interactive() {
+        assert!(interactive(false, true, false));
+
+        // No TTY, `--yes` or CI
+        assert!(!interactive(false, false, false));
+        assert!(!interactive(true, true, false));
+        assert!(!interactive(false, true, true));
+    }
+
+    #[test]
+    fn test_non_interactive_defaults() {
+        let t = Terminal { interactive: false };
+
+        assert_eq!(t.prompt("name?", Some("x".into())).unwrap(), "x");
+        assert!(t.confirm("sure?", Some(true)).unwrap());
+
+        let items = vec!["a".to_owned(), "b".to_owned()];
+        assert_eq!(t.select("pick", &items, Some(1)).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_non_interactive_without_default() {
+        let t = Terminal { interactive: false };
+
+        assert!(matches!(
+            t.prompt("name?", None),
+            Err(PromptError::NonInteractive)
+        ));
+
+        assert!(matches!(
+            t.confirm("sure?", None),
+            Err(PromptError::NonInteractive)
+        ));
+
+        assert!(matches!(
+            t.select("pick", &["a".to_owned()], None),
+            Err(PromptError::NonInteractive)
+        ));
+
+        assert!(matches!(
+            t.password("secret?"),
+            Err(PromptError::NonInteractive)
+        ));
+    }
+
+    #[test]
+    fn test_select_invalid() {
+        let t = Terminal { interactive: false };
+
+        assert!(matches!(
+            t.select("pick", &[], None),
+            Err(PromptError::Invalid(_))
+        ));
+
+        assert!(matches!(
+            t.select("pick", &["a".to_owned()], Some(1)),
+            Err(PromptError::Invalid(_))
+        ));
+    }
+}
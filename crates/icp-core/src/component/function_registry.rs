@@ -22,6 +22,9 @@ pub enum FunctionRegistryError {
 pub struct FunctionRegistry {
     /// Map from reference key to function reference
     references: HashMap<String, Arc<Mutex<Option<Func>>>>,
+
+    /// Map from reference key to the name of the extension providing the function
+    providers: HashMap<String, String>,
 }
 
 impl FunctionRegistry {
@@ -29,6 +32,7 @@ impl FunctionRegistry {
     pub fn new() -> Self {
         Self {
             references: HashMap::new(),
+            providers: HashMap::new(),
         }
     }
 }
@@ -69,13 +73,19 @@ impl FunctionRegistry {
     /// # Arguments
     ///
     /// * `k` - Key of the function reference to resolve
+    /// * `provider` - Name of the extension exporting the function
     /// * `f` - Function to resolve the reference to
     ///
     /// # Returns
     ///
     /// * `Ok(())` if resolution succeeded
     /// * `Err(FunctionRegistryError::NotFound)` if key not found
-    pub fn resolve(&self, k: &str, f: Func) -> Result<(), FunctionRegistryError> {
+    pub fn resolve(
+        &mut self,
+        k: &str,
+        provider: &str,
+        f: Func,
+    ) -> Result<(), FunctionRegistryError> {
         let reference = self
             .references
             .get(k)
@@ -83,9 +93,17 @@ impl FunctionRegistry {
 
         let mut g = reference.lock().unwrap();
         *g = Some(f);
+
+        self.providers.insert(k.to_string(), provider.to_string());
+
         Ok(())
     }
 
+    /// Get the name of the extension providing a resolved function reference
+    pub fn provider(&self, k: &str) -> Option<&str> {
+        self.providers.get(k).map(|p| p.as_str())
+    }
+
     /// Look up a resolved function reference by its interface and function name.
     ///
     /// # Arguments
@@ -5,7 +5,7 @@ use std::{
 
 use anyhow::{anyhow, Context};
 use wasmtime::component::{Instance, Linker};
-use wasmtime::Store;
+use wasmtime::{AsContextMut, Store, StoreContextMut};
 
 use crate::{FunctionRegistry, FunctionRegistryError, Interface};
 
@@ -25,6 +25,38 @@ pub enum DynamicLinkingError {
     UnexpectedError(#[from] anyhow::Error),
 }
 
+/// Store data that is notified of the calls forwarded between extensions
+///
+/// The dynamic linker forwards calls from one extension to the functions exported by another.
+/// Implementations can use these notifications to attribute resource usage to the callee.
+pub trait CallTracker: Sized {
+    /// Called before calling `function`, exported by `extension`
+    fn enter(
+        store: StoreContextMut<'_, Self>,
+        extension: &str,
+        function: &str,
+    ) -> Result<(), anyhow::Error>;
+
+    /// Called once the call returns, with its outcome (which may be replaced)
+    fn exit(
+        store: StoreContextMut<'_, Self>,
+        out: Result<(), anyhow::Error>,
+    ) -> Result<(), anyhow::Error>;
+}
+
+impl CallTracker for () {
+    fn enter(_: StoreContextMut<'_, Self>, _: &str, _: &str) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+
+    fn exit(
+        _: StoreContextMut<'_, Self>,
+        out: Result<(), anyhow::Error>,
+    ) -> Result<(), anyhow::Error> {
+        out
+    }
+}
+
 /// Dynamic linker for WebAssembly components
 ///
 /// The dynamic linker manages function references between extensions,
@@ -70,7 +102,7 @@ impl DynamicLinker {
     /// this method will likely panic due to the underlying Wasmtime linker
     /// rejecting duplicate instance definitions. The caller is responsible for
     /// ensuring the uniqueness of interface names in the input vector.
-    pub fn link<T: Send>(
+    pub fn link<T: Send + CallTracker>(
         &mut self,
         lnk: &mut Linker<T>,
         ifaces: Vec<Interface>,
@@ -100,27 +132,43 @@ impl DynamicLinker {
                 registry.register(k.clone(), Arc::clone(&fref))?;
 
                 let fname = f.clone();
+                let registry = Arc::clone(&self.registry);
 
                 inst.func_new_async(&f, move |mut store, params, results| {
+                    let k = k.clone();
                     let fname = fname.clone();
                     let fref = Arc::clone(&fref);
+                    let registry = Arc::clone(&registry);
 
                     Box::new(async move {
                         let f = {
                             let g = fref.lock().unwrap();
-                            *g.as_ref()
-                                .ok_or_else(|| DynamicLinkingError::UnresolvedReference(fname))?
+                            *g.as_ref().ok_or_else(|| {
+                                DynamicLinkingError::UnresolvedReference(fname.clone())
+                            })?
                         };
 
-                        f.call_async(&mut store, params, results)
-                            .await
-                            .context("call failed")?;
+                        let provider = registry
+                            .lock()
+                            .unwrap()
+                            .provider(&k)
+                            .unwrap_or_default()
+                            .to_string();
+
+                        T::enter(store.as_context_mut(), &provider, &fname)?;
+
+                        let out = async {
+                            f.call_async(&mut store, params, results)
+                                .await
+                                .context("call failed")?;
 
-                        f.post_return_async(&mut store)
-                            .await
-                            .context("post-return failed")?;
+                            f.post_return_async(&mut store)
+                                .await
+                                .context("post-return failed")
+                        }
+                        .await;
 
-                        Ok(())
+                        T::exit(store.as_context_mut(), out)
                     })
                 })?;
             }
@@ -189,7 +237,7 @@ impl DynamicLinker {
                     )
                     .ok_or(anyhow!("missing function"))?;
 
-                self.registry.lock().unwrap().resolve(&k, f)?;
+                self.registry.lock().unwrap().resolve(&k, extension, f)?;
             }
         }
 
@@ -284,7 +332,7 @@ mod tests {
             vec![iface2], // interfaces
         );
 
-        if !out.is_err() {
+        if out.is_ok() {
             bail!("expected second linking call to fail because of duplicate interface name");
         }
 
@@ -7,4 +7,4 @@ pub mod function_registry;
 pub mod linker;
 
 pub use function_registry::{FunctionRegistry, FunctionRegistryError};
-pub use linker::{DynamicLinker, DynamicLinkingError};
+pub use linker::{CallTracker, DynamicLinker, DynamicLinkingError};
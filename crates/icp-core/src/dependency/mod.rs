@@ -3,10 +3,20 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
 use anyhow::Context;
+use serde::Serialize;
 use thiserror::Error;
 
 use crate::manifest::{Extension, Manifest};
 
+/// Check whether an extension registers with the provider of an interface it imports during
+/// `init` (the way build providers register with the build facade), as declared in the
+/// `registers` field of its spec
+fn registers_through(x: &Extension, interface: &str) -> bool {
+    x.spec
+        .as_ref()
+        .is_some_and(|spec| spec.registers_through(interface))
+}
+
 /// Errors that can occur during dependency resolution
 #[derive(Debug, Error)]
 pub enum DependencyError {
@@ -45,6 +55,22 @@ pub enum DependencyError {
     UnexpectedError(#[from] anyhow::Error),
 }
 
+/// An edge in the dependency graph, describing why one extension depends on another
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DependencyEdge {
+    /// Name of the extension that imports the interface
+    pub importer: String,
+
+    /// Name of the extension that exports the interface, if any
+    pub provider: Option<String>,
+
+    /// Name of the interface that causes the dependency
+    pub interface: String,
+
+    /// Names of the functions imported from the interface
+    pub funcs: Vec<String>,
+}
+
 /// A graph representing dependencies between extensions
 #[derive(Debug)]
 pub struct DependencyGraph {
@@ -54,6 +80,9 @@ pub struct DependencyGraph {
     /// Map from extension name to the names of extensions that depend on it
     dependents: HashMap<String, Vec<String>>,
 
+    /// Map from extension name to the names of extensions registering with it during `init`
+    registrants: HashMap<String, Vec<String>>,
+
     /// Map from interface name to the extension that exports it
     interface_providers: HashMap<String, String>,
 
@@ -66,6 +95,9 @@ pub struct DependencyGraph {
     /// Map from interface name to the functions it provides
     interface_functions: HashMap<String, HashSet<String>>,
 
+    /// Edges between extensions, one per imported interface
+    edges: Vec<DependencyEdge>,
+
     /// All extension names in the graph
     extension_names: Vec<String>,
 
@@ -79,10 +111,12 @@ impl DependencyGraph {
         let mut g = Self {
             dependencies: HashMap::new(),
             dependents: HashMap::new(),
+            registrants: HashMap::new(),
             interface_providers: HashMap::new(),
             exports: HashMap::new(),
             imports: HashMap::new(),
             interface_functions: HashMap::new(),
+            edges: Vec::new(),
             extension_names: Vec::new(),
             cycles: Vec::new(),
         };
@@ -150,6 +184,13 @@ impl DependencyGraph {
         // Build dependency edges
         for x in &m.xs {
             for imp in &x.imports {
+                self.edges.push(DependencyEdge {
+                    importer: x.name.clone(),
+                    provider: self.interface_providers.get(&imp.name).cloned(),
+                    interface: imp.name.clone(),
+                    funcs: imp.funcs.clone(),
+                });
+
                 if let Some(p) = self.interface_providers.get(&imp.name) {
                     // Add dependency edge
                     if let Some(deps) = self.dependencies.get_mut(&x.name) {
@@ -164,6 +205,14 @@ impl DependencyGraph {
                             deps.push(x.name.clone());
                         }
                     }
+
+                    // Add registration edge
+                    if registers_through(x, &imp.name) {
+                        let xs = self.registrants.entry(p.clone()).or_default();
+                        if !xs.contains(&x.name) {
+                            xs.push(x.name.clone());
+                        }
+                    }
                 }
             }
         }
@@ -300,6 +349,41 @@ impl DependencyGraph {
         Ok(result)
     }
 
+    /// Resolves the loading order of the extensions needed to run a single extension
+    ///
+    /// This includes the extension itself and its transitive providers, as well as the
+    /// extensions registering with any of them (as declared in the `registers` field of their
+    /// spec), along with their own providers. Other dependents are not loaded.
+    pub fn resolve_loading_order_for(
+        &self,
+        extension: &str,
+    ) -> Result<Vec<String>, DependencyError> {
+        let mut required = HashSet::new();
+        let mut queue = VecDeque::from([extension.to_string()]);
+
+        while let Some(ext) = queue.pop_front() {
+            if !required.insert(ext.clone()) {
+                continue;
+            }
+
+            // Providers
+            if let Some(deps) = self.dependencies.get(&ext) {
+                queue.extend(deps.iter().cloned());
+            }
+
+            // Registrants
+            if let Some(xs) = self.registrants.get(&ext) {
+                queue.extend(xs.iter().cloned());
+            }
+        }
+
+        Ok(self
+            .resolve_loading_order()?
+            .into_iter()
+            .filter(|x| required.contains(x))
+            .collect())
+    }
+
     /// Validates that all dependencies are satisfied
     pub fn validate_dependencies(&self, m: &Manifest) -> Result<(), DependencyError> {
         for x in &m.xs {
@@ -387,6 +471,144 @@ impl DependencyGraph {
     }
 }
 
+/// A change to the interfaces exported by an extension, e.g. between two of its versions
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum InterfaceChange {
+    /// A new interface is exported
+    AddedInterface {
+        /// Name of the interface
+        interface: String,
+    },
+
+    /// A new function is exported from an interface that was already exported
+    AddedFunction {
+        /// Name of the interface
+        interface: String,
+
+        /// Name of the function
+        function: String,
+    },
+
+    /// An interface is no longer exported
+    RemovedInterface {
+        /// Name of the interface
+        interface: String,
+
+        /// Names of the extensions importing the interface
+        dependents: Vec<String>,
+    },
+
+    /// A function is no longer exported from an interface that is still exported
+    RemovedFunction {
+        /// Name of the interface
+        interface: String,
+
+        /// Name of the function
+        function: String,
+
+        /// Names of the extensions importing the function
+        dependents: Vec<String>,
+    },
+}
+
+impl InterfaceChange {
+    /// Check if the change breaks installed extensions
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            Self::RemovedInterface { dependents, .. }
+            | Self::RemovedFunction { dependents, .. } => !dependents.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for InterfaceChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let used_by = |dependents: &[String]| match dependents.is_empty() {
+            true => String::new(),
+            false => format!(" (used by {})", dependents.join(", ")),
+        };
+
+        match self {
+            Self::AddedInterface { interface } => write!(f, "+ interface {interface}"),
+            Self::AddedFunction {
+                interface,
+                function,
+            } => write!(f, "+ function {interface}.{function}"),
+            Self::RemovedInterface {
+                interface,
+                dependents,
+            } => write!(f, "- interface {interface}{}", used_by(dependents)),
+            Self::RemovedFunction {
+                interface,
+                function,
+                dependents,
+            } => write!(
+                f,
+                "- function {interface}.{function}{}",
+                used_by(dependents)
+            ),
+        }
+    }
+}
+
+/// Lists the changes between the interfaces exported by two versions of an extension, along
+/// with the other extensions of the manifest importing what is no longer exported
+pub fn export_changes(old: &Extension, new: &Extension, m: &Manifest) -> Vec<InterfaceChange> {
+    // Extensions importing a function (or any function, if none is given) of an interface
+    let dependents = |interface: &str, function: Option<&str>| -> Vec<String> {
+        m.xs.iter()
+            .filter(|x| x.name != old.name)
+            .filter(|x| {
+                x.imports.iter().any(|imp| {
+                    imp.name == interface
+                        && function.is_none_or(|f| imp.funcs.iter().any(|g| g == f))
+                })
+            })
+            .map(|x| x.name.clone())
+            .collect()
+    };
+
+    let mut changes = vec![];
+
+    for exp in &old.exports {
+        let Some(next) = new.exports.iter().find(|e| e.name == exp.name) else {
+            changes.push(InterfaceChange::RemovedInterface {
+                interface: exp.name.clone(),
+                dependents: dependents(&exp.name, None),
+            });
+
+            continue;
+        };
+
+        for function in exp.funcs.iter().filter(|f| !next.funcs.contains(f)) {
+            changes.push(InterfaceChange::RemovedFunction {
+                interface: exp.name.clone(),
+                function: function.clone(),
+                dependents: dependents(&exp.name, Some(function)),
+            });
+        }
+
+        for function in next.funcs.iter().filter(|f| !exp.funcs.contains(f)) {
+            changes.push(InterfaceChange::AddedFunction {
+                interface: exp.name.clone(),
+                function: function.clone(),
+            });
+        }
+    }
+
+    for exp in &new.exports {
+        if !old.exports.iter().any(|e| e.name == exp.name) {
+            changes.push(InterfaceChange::AddedInterface {
+                interface: exp.name.clone(),
+            });
+        }
+    }
+
+    changes
+}
+
 impl DependencyGraph {
     /// Formats a text representation of the dependency graph
     pub fn format_text_representation(&self) -> String {
@@ -456,12 +678,196 @@ impl DependencyGraph {
     }
 }
 
+impl DependencyGraph {
+    /// Checks if an extension is part of the graph
+    pub fn contains(&self, extension: &str) -> bool {
+        self.extension_names.iter().any(|x| x == extension)
+    }
+
+    /// Gets the edges originating from an extension, one per imported interface
+    pub fn edges(&self, extension: &str) -> Vec<&DependencyEdge> {
+        self.edges
+            .iter()
+            .filter(|e| e.importer == extension)
+            .collect()
+    }
+
+    /// Gets the extensions reachable from a root (inclusive), in graph order.
+    /// When no root is given, all extensions are returned.
+    fn reachable(&self, root: Option<&str>) -> Vec<String> {
+        let root = match root {
+            Some(root) => root,
+            None => return self.extension_names.clone(),
+        };
+
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([root.to_string()]);
+
+        while let Some(ext) = queue.pop_front() {
+            if !seen.insert(ext.clone()) {
+                continue;
+            }
+
+            if let Some(deps) = self.dependencies.get(&ext) {
+                queue.extend(deps.iter().cloned());
+            }
+        }
+
+        self.extension_names
+            .iter()
+            .filter(|x| seen.contains(*x))
+            .cloned()
+            .collect()
+    }
+}
+
+impl DependencyGraph {
+    /// Formats a dependency tree for a single extension, or for all extensions
+    pub fn format_tree(&self, root: Option<&str>) -> String {
+        let roots = match root {
+            Some(root) => vec![root.to_string()],
+            None => self.extension_names.clone(),
+        };
+
+        let mut result = String::new();
+
+        for ext in roots {
+            result.push_str(&format!("{}\n", ext));
+
+            let mut path = vec![ext.clone()];
+            self.write_tree(&ext, "", &mut path, &mut result);
+
+            result.push('\n');
+        }
+
+        result
+    }
+
+    /// Recursive helper for writing the dependencies of an extension as a tree
+    fn write_tree(&self, ext: &str, prefix: &str, path: &mut Vec<String>, result: &mut String) {
+        let edges = self.edges(ext);
+
+        for (i, e) in edges.iter().enumerate() {
+            let (branch, indent) = if i == edges.len() - 1 {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+
+            let label = format!("{} [{}]", e.interface, e.funcs.join(", "));
+
+            match &e.provider {
+                // Cycle
+                Some(p) if path.contains(p) => {
+                    result.push_str(&format!(
+                        "{}{}{} via {} (cycle)\n",
+                        prefix, branch, p, label
+                    ));
+                }
+
+                // Provider
+                Some(p) => {
+                    result.push_str(&format!("{}{}{} via {}\n", prefix, branch, p, label));
+
+                    path.push(p.clone());
+                    self.write_tree(p, &format!("{}{}", prefix, indent), path, result);
+                    path.pop();
+                }
+
+                // Missing
+                None => {
+                    result.push_str(&format!(
+                        "{}{}{} (provider not found)\n",
+                        prefix, branch, label
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Formats the dependency graph in Graphviz DOT format
+    pub fn format_dot(&self, root: Option<&str>) -> String {
+        let exts = self.reachable(root);
+
+        let mut result = String::from("digraph dependencies {\n");
+
+        for ext in &exts {
+            result.push_str(&format!("    {};\n", dot_id(ext)));
+        }
+
+        for ext in &exts {
+            for e in self.edges(ext) {
+                let label = dot_id(&format!("{}\n{}", e.interface, e.funcs.join("\n")));
+
+                match &e.provider {
+                    Some(p) => {
+                        result.push_str(&format!(
+                            "    {} -> {} [label={}];\n",
+                            dot_id(ext),
+                            dot_id(p),
+                            label
+                        ));
+                    }
+
+                    None => {
+                        let missing = dot_id(&format!("{} (missing)", e.interface));
+
+                        result.push_str(&format!("    {} [shape=box, style=dashed];\n", missing));
+                        result.push_str(&format!(
+                            "    {} -> {} [label={}, style=dashed];\n",
+                            dot_id(ext),
+                            missing,
+                            label
+                        ));
+                    }
+                }
+            }
+        }
+
+        result.push_str("}\n");
+        result
+    }
+
+    /// Formats the dependency graph as JSON
+    pub fn format_json(&self, root: Option<&str>) -> Result<String, DependencyError> {
+        #[derive(Serialize)]
+        struct Output<'a> {
+            extensions: Vec<String>,
+            edges: Vec<&'a DependencyEdge>,
+            cycles: &'a [Vec<String>],
+        }
+
+        let extensions = self.reachable(root);
+
+        let edges = extensions.iter().flat_map(|ext| self.edges(ext)).collect();
+
+        let out = serde_json::to_string_pretty(&Output {
+            extensions,
+            edges,
+            cycles: &self.cycles,
+        })
+        .context("failed to serialize dependency graph")?;
+
+        Ok(out)
+    }
+}
+
+/// Quotes a string for use as an identifier or label in DOT
+fn dot_id(s: &str) -> String {
+    format!(
+        "\"{}\"",
+        s.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Error;
 
     use super::*;
-    use crate::manifest::Interface;
+    use crate::{manifest::Interface, spec::CommandSpec};
 
     fn create_test_manifest() -> Manifest {
         let mut m = Manifest::default();
@@ -470,6 +876,15 @@ mod tests {
             wasm: "ext-a.wasm".into(),
             pre: "ext-a.bin".into(),
             checksum: None,
+            compatibility_hash: None,
+            permissions: Default::default(),
+            limits: Default::default(),
+            wasi: false,
+            spec: None,
+            metadata: Default::default(),
+            source: None,
+            registry: None,
+            installed_at: None,
             imports: Vec::new(),
             exports: vec![Interface {
                 name: "math/lib".to_string(),
@@ -483,6 +898,15 @@ mod tests {
             wasm: "ext-b.wasm".into(),
             pre: "ext-b.bin".into(),
             checksum: None,
+            compatibility_hash: None,
+            permissions: Default::default(),
+            limits: Default::default(),
+            wasi: false,
+            spec: None,
+            metadata: Default::default(),
+            source: None,
+            registry: None,
+            installed_at: None,
             imports: vec![Interface {
                 name: "math/lib".to_string(),
                 funcs: vec!["add".to_string()],
@@ -499,6 +923,15 @@ mod tests {
             wasm: "ext-c.wasm".into(),
             pre: "ext-c.bin".into(),
             checksum: None,
+            compatibility_hash: None,
+            permissions: Default::default(),
+            limits: Default::default(),
+            wasi: false,
+            spec: None,
+            metadata: Default::default(),
+            source: None,
+            registry: None,
+            installed_at: None,
             imports: vec![Interface {
                 name: "calc/lib".to_string(),
                 funcs: vec!["calculate".to_string()],
@@ -516,6 +949,15 @@ mod tests {
             wasm: "ext-a.wasm".into(),
             pre: "ext-a.bin".into(),
             checksum: None,
+            compatibility_hash: None,
+            permissions: Default::default(),
+            limits: Default::default(),
+            wasi: false,
+            spec: None,
+            metadata: Default::default(),
+            source: None,
+            registry: None,
+            installed_at: None,
             exports: vec![Interface {
                 name: "a/lib".to_string(),
                 funcs: vec!["func_a".to_string()],
@@ -532,6 +974,15 @@ mod tests {
             wasm: "ext-b.wasm".into(),
             pre: "ext-b.bin".into(),
             checksum: None,
+            compatibility_hash: None,
+            permissions: Default::default(),
+            limits: Default::default(),
+            wasi: false,
+            spec: None,
+            metadata: Default::default(),
+            source: None,
+            registry: None,
+            installed_at: None,
             imports: vec![Interface {
                 name: "a/lib".to_string(),
                 funcs: vec!["func_a".to_string()],
@@ -548,6 +999,15 @@ mod tests {
             wasm: "ext-c.wasm".into(),
             pre: "ext-c.bin".into(),
             checksum: None,
+            compatibility_hash: None,
+            permissions: Default::default(),
+            limits: Default::default(),
+            wasi: false,
+            spec: None,
+            metadata: Default::default(),
+            source: None,
+            registry: None,
+            installed_at: None,
             imports: vec![Interface {
                 name: "b/lib".to_string(),
                 funcs: vec!["func_b".to_string()],
@@ -607,6 +1067,88 @@ mod tests {
         assert_eq!(order[2], "ext-c");
     }
 
+    #[test]
+    fn test_resolve_loading_order_for() -> Result<(), Error> {
+        let mut manifest = create_test_manifest();
+
+        // Extension D is unrelated to the others
+        manifest.xs.push(Extension {
+            name: "ext-d".to_string(),
+            wasm: "ext-d.wasm".into(),
+            pre: "ext-d.bin".into(),
+            checksum: None,
+            compatibility_hash: None,
+            permissions: Default::default(),
+            limits: Default::default(),
+            wasi: false,
+            spec: None,
+            metadata: Default::default(),
+            source: None,
+            registry: None,
+            installed_at: None,
+            imports: Vec::new(),
+            exports: Vec::new(),
+        });
+
+        let graph = DependencyGraph::new(&manifest)?;
+
+        assert_eq!(graph.resolve_loading_order_for("ext-d")?, vec!["ext-d"]);
+
+        // Only providers are loaded, not dependents
+        assert_eq!(
+            graph.resolve_loading_order_for("ext-b")?,
+            vec!["ext-a", "ext-b"]
+        );
+
+        assert_eq!(
+            graph.resolve_loading_order_for("ext-c")?,
+            vec!["ext-a", "ext-b", "ext-c"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_loading_order_for_registrants() -> Result<(), Error> {
+        let mut m = create_test_manifest();
+
+        // Extension A also exports a registration interface, which extension R registers with,
+        // while extension I imports an interface of A without registering with it
+        m.xs[0].exports.push(Interface {
+            name: "math/providers@1.0.0".to_string(),
+            funcs: vec!["register".to_string()],
+        });
+
+        for name in ["ext-r", "ext-i"] {
+            let mut x = m.xs[2].clone();
+            x.name = name.to_string();
+            x.imports = vec![Interface {
+                name: "math/providers@1.0.0".to_string(),
+                funcs: vec!["register".to_string()],
+            }];
+            m.xs.push(x);
+        }
+
+        m.xs[3].spec = Some(CommandSpec::parse(
+            r#"{ "name": "ext-r", "registers": ["math/providers"] }"#,
+        )?);
+
+        let graph = DependencyGraph::new(&m)?;
+
+        // Registrants of providers are loaded, other dependents are not
+        assert_eq!(
+            graph.resolve_loading_order_for("ext-b")?,
+            vec!["ext-a", "ext-b", "ext-r"]
+        );
+
+        assert_eq!(
+            graph.resolve_loading_order_for("ext-r")?,
+            vec!["ext-a", "ext-r"]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_cycle_detection() {
         let manifest = create_cyclic_manifest();
@@ -640,6 +1182,15 @@ mod tests {
             wasm: "ext-d.wasm".into(),
             pre: "ext-d.bin".into(),
             checksum: None,
+            compatibility_hash: None,
+            permissions: Default::default(),
+            limits: Default::default(),
+            wasi: false,
+            spec: None,
+            metadata: Default::default(),
+            source: None,
+            registry: None,
+            installed_at: None,
             imports: vec![Interface {
                 name: "missing/lib".to_string(),
                 funcs: vec!["func".to_string()],
@@ -669,4 +1220,116 @@ mod tests {
         assert!(text.contains("Extension: ext-b"));
         assert!(text.contains("Extension: ext-c"));
     }
+
+    #[test]
+    fn test_edges() {
+        let graph = DependencyGraph::new(&create_test_manifest()).unwrap();
+
+        let edges = graph.edges("ext-b");
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].provider.as_deref(), Some("ext-a"));
+        assert_eq!(edges[0].interface, "math/lib");
+        assert_eq!(edges[0].funcs, vec!["add".to_string()]);
+
+        assert!(graph.edges("ext-a").is_empty());
+    }
+
+    #[test]
+    fn test_tree_representation() {
+        let graph = DependencyGraph::new(&create_test_manifest()).unwrap();
+
+        let tree = graph.format_tree(Some("ext-c"));
+        assert_eq!(
+            tree,
+            "ext-c\n\
+             └── ext-b via calc/lib [calculate]\n\
+             \x20   └── ext-a via math/lib [add]\n\n"
+        );
+
+        let tree = graph.format_tree(None);
+        assert!(tree.contains("ext-a\n"));
+        assert!(tree.contains("ext-b\n└── ext-a via math/lib [add]\n"));
+    }
+
+    #[test]
+    fn test_tree_representation_cycle() {
+        let graph = DependencyGraph::new(&create_cyclic_manifest()).unwrap();
+
+        let tree = graph.format_tree(Some("ext-a"));
+        assert!(tree.contains("ext-a via a/lib [func_a] (cycle)"));
+    }
+
+    #[test]
+    fn test_dot_representation() {
+        let graph = DependencyGraph::new(&create_test_manifest()).unwrap();
+
+        let dot = graph.format_dot(Some("ext-b"));
+        assert!(dot.starts_with("digraph dependencies {\n"));
+        assert!(dot.contains("\"ext-b\" -> \"ext-a\" [label=\"math/lib\\nadd\"];"));
+        assert!(!dot.contains("ext-c"));
+    }
+
+    #[test]
+    fn test_export_changes() {
+        let m = create_test_manifest();
+        let old = &m.xs[0];
+
+        let mut new = old.clone();
+        new.exports = vec![
+            Interface {
+                name: "math/lib".to_string(),
+                funcs: vec!["add".to_string(), "multiply".to_string()],
+            },
+            Interface {
+                name: "stats/lib".to_string(),
+                funcs: vec!["mean".to_string()],
+            },
+        ];
+
+        let changes = export_changes(old, &new, &m);
+
+        assert_eq!(
+            changes,
+            vec![
+                InterfaceChange::RemovedFunction {
+                    interface: "math/lib".to_string(),
+                    function: "subtract".to_string(),
+                    dependents: vec![],
+                },
+                InterfaceChange::AddedFunction {
+                    interface: "math/lib".to_string(),
+                    function: "multiply".to_string(),
+                },
+                InterfaceChange::AddedInterface {
+                    interface: "stats/lib".to_string(),
+                },
+            ]
+        );
+        assert!(!changes.iter().any(InterfaceChange::is_breaking));
+
+        // ext-b imports math/lib
+        new.exports.remove(0);
+
+        let changes = export_changes(old, &new, &m);
+
+        assert!(changes[0].is_breaking());
+        assert_eq!(
+            changes[0].to_string(),
+            "- interface math/lib (used by ext-b)"
+        );
+    }
+
+    #[test]
+    fn test_json_representation() -> Result<(), Error> {
+        let graph = DependencyGraph::new(&create_test_manifest())?;
+
+        let out: serde_json::Value = serde_json::from_str(&graph.format_json(None)?)?;
+        assert_eq!(out["extensions"].as_array().unwrap().len(), 3);
+        assert_eq!(out["edges"].as_array().unwrap().len(), 2);
+        assert_eq!(out["edges"][0]["importer"], "ext-b");
+        assert_eq!(out["edges"][0]["provider"], "ext-a");
+        assert_eq!(out["edges"][0]["interface"], "math/lib");
+
+        Ok(())
+    }
 }
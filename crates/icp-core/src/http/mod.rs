@@ -0,0 +1,375 @@
+//! Outbound HTTP for icp extensions.
+//!
+//! Extensions send HTTP requests through the host, which only lets them reach the hosts
+//! allowed for them in the manifest and enforces a timeout on every request. Redirects are
+//! followed only as long as they stay within the allowed hosts.
+
+use std::time::Duration;
+
+use reqwest::{
+    redirect::{Attempt, Policy},
+    Client, Method, Url,
+};
+use thiserror::Error;
+
+/// Maximum number of redirects followed for a single request
+const MAX_REDIRECTS: usize = 10;
+
+/// Errors that can occur while sending an HTTP request
+#[derive(Debug, Error)]
+pub enum HttpError {
+    /// The URL's host is not in the allowlist
+    #[error("host {0} is not allowed")]
+    HostNotAllowed(String),
+
+    /// The request is malformed (e.g. invalid method, URL or header)
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
+    /// The request did not complete in time
+    #[error("request timed out after {0:?}")]
+    Timeout(Duration),
+
+    /// The request failed (e.g. connection refused)
+    #[error("request failed: {0}")]
+    Transport(String),
+}
+
+/// An HTTP request
+#[derive(Debug, Clone, Default)]
+pub struct Request {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+/// An HTTP response
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// Hosts an extension is allowed to send requests to
+#[derive(Debug, Clone, Default)]
+pub struct Allowlist(Vec<String>);
+
+impl Allowlist {
+    /// Create an allowlist
+    ///
+    /// # Arguments
+    ///
+    /// * `hosts` - Entries of the form `host` (any port) or `host:port`, where the host may
+    ///   start with `*.` to match any of its subdomains
+    pub fn new(hosts: impl IntoIterator<Item = String>) -> Self {
+        Self(hosts.into_iter().map(|h| h.to_lowercase()).collect())
+    }
+
+    /// Check if a URL's host (and port) is allowed
+    pub fn allows(&self, url: &Url) -> bool {
+        let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+            return false;
+        };
+
+        self.0.iter().any(|entry| {
+            // IPv6 addresses are written in brackets, like in URLs (e.g. `[::1]:8080`)
+            let (pattern, p) = match entry.rsplit_once(':') {
+                Some((h, p)) if !h.contains(':') || h.ends_with(']') => (h, Some(p)),
+                _ => (entry.as_str(), None),
+            };
+
+            if p.is_some_and(|p| p != port.to_string()) {
+                return false;
+            }
+
+            match pattern.strip_prefix("*.") {
+                Some(domain) => host
+                    .strip_suffix(domain)
+                    .is_some_and(|sub| sub.ends_with('.') && sub.len() > 1),
+                None => host == pattern,
+            }
+        })
+    }
+}
+
+/// HTTP client confined to an allowlist of hosts
+pub struct HttpClient {
+    allowlist: Allowlist,
+    timeout: Duration,
+
+    /// Created on first use, since most extensions never send requests
+    client: Option<Client>,
+}
+
+impl HttpClient {
+    /// Create a client
+    ///
+    /// # Arguments
+    ///
+    /// * `allowlist` - Hosts requests may be sent to
+    /// * `timeout` - Time allowed for each request to complete, including reading the response
+    pub fn new(allowlist: Allowlist, timeout: Duration) -> Self {
+        Self {
+            allowlist,
+            timeout,
+            client: None,
+        }
+    }
+
+    /// Send a request
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Response)` with the response, whatever its status
+    /// * `Err(HttpError::HostNotAllowed)` if the URL (or a redirect) leads outside of the allowlist
+    /// * `Err(HttpError::Timeout)` if the request did not complete in time
+    pub async fn send(&mut self, req: Request) -> Result<Response, HttpError> {
+        let url = Url::parse(&req.url).map_err(|err| HttpError::InvalidRequest(err.to_string()))?;
+
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(HttpError::InvalidRequest(format!(
+                "unsupported scheme {}",
+                url.scheme()
+            )));
+        }
+
+        if !self.allowlist.allows(&url) {
+            return Err(HttpError::HostNotAllowed(host(&url)));
+        }
+
+        let method = Method::from_bytes(req.method.to_uppercase().as_bytes())
+            .map_err(|err| HttpError::InvalidRequest(err.to_string()))?;
+
+        let client = match &self.client {
+            Some(client) => client,
+            None => self.client.insert(self.build_client()?),
+        };
+
+        let mut rb = client.request(method, url);
+
+        for (k, v) in req.headers {
+            rb = rb.header(k, v);
+        }
+
+        if let Some(body) = req.body {
+            rb = rb.body(body);
+        }
+
+        let res = rb.send().await.map_err(|err| self.error(err))?;
+
+        let status = res.status().as_u16();
+
+        let headers = res
+            .headers()
+            .iter()
+            .map(|(k, v)| {
+                (
+                    k.to_string(),
+                    String::from_utf8_lossy(v.as_bytes()).into_owned(),
+                )
+            })
+            .collect();
+
+        let body = res.bytes().await.map_err(|err| self.error(err))?.to_vec();
+
+        Ok(Response {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    fn build_client(&self) -> Result<Client, HttpError> {
+        let allowlist = self.allowlist.clone();
+
+        let policy = Policy::custom(move |attempt: Attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                return attempt.error("too many redirects");
+            }
+
+            match allowlist.allows(attempt.url()) {
+                true => attempt.follow(),
+                false => {
+                    let err = HttpError::HostNotAllowed(host(attempt.url()));
+                    attempt.error(err)
+                }
+            }
+        });
+
+        Client::builder()
+            .timeout(self.timeout)
+            .redirect(policy)
+            .build()
+            .map_err(|err| HttpError::Transport(err.to_string()))
+    }
+
+    fn error(&self, err: reqwest::Error) -> HttpError {
+        if err.is_timeout() {
+            return HttpError::Timeout(self.timeout);
+        }
+
+        if err.is_builder() {
+            return HttpError::InvalidRequest(err.to_string());
+        }
+
+        // Redirects refused by the policy
+        let mut source = std::error::Error::source(&err);
+        while let Some(s) = source {
+            if let Some(HttpError::HostNotAllowed(host)) = s.downcast_ref::<HttpError>() {
+                return HttpError::HostNotAllowed(host.clone());
+            }
+            source = s.source();
+        }
+
+        HttpError::Transport(err.to_string())
+    }
+}
+
+/// Host and port of a URL, for error messages
+fn host(url: &Url) -> String {
+    match (url.host_str(), url.port_or_known_default()) {
+        (Some(h), Some(p)) => format!("{h}:{p}"),
+        (Some(h), None) => h.to_owned(),
+        _ => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::oneshot,
+    };
+
+    use super::*;
+
+    /// Serves a single connection with the given raw response (or never responds if `None`),
+    /// returning the server's address and the raw request it received
+    async fn serve(response: Option<String>) -> (String, oneshot::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let (tx, rx) = oneshot::channel();
+
+        tokio::spawn(async move {
+            let (mut conn, _) = listener.accept().await.unwrap();
+
+            let mut buf = vec![0; 4096];
+            let n = conn.read(&mut buf).await.unwrap();
+            let _ = tx.send(String::from_utf8_lossy(&buf[..n]).into_owned());
+
+            match response {
+                Some(r) => conn.write_all(r.as_bytes()).await.unwrap(),
+                None => tokio::time::sleep(Duration::from_secs(10)).await,
+            }
+        });
+
+        (addr, rx)
+    }
+
+    fn client(hosts: &[&str], timeout: Duration) -> HttpClient {
+        HttpClient::new(Allowlist::new(hosts.iter().map(|h| h.to_string())), timeout)
+    }
+
+    #[test]
+    fn test_allowlist() {
+        let a = Allowlist::new([
+            "ic0.app".into(),
+            "*.icp0.io".into(),
+            "localhost:4943".into(),
+        ]);
+        let allows = |u: &str| a.allows(&Url::parse(u).unwrap());
+
+        assert!(allows("https://ic0.app/api"));
+        assert!(allows("http://IC0.APP:8080"));
+        assert!(allows("https://a.b.icp0.io"));
+        assert!(allows("http://localhost:4943"));
+        assert!(Allowlist::new(["[::1]".into()]).allows(&Url::parse("http://[::1]:80").unwrap()));
+
+        assert!(!allows("https://icp0.io"));
+        assert!(!allows("https://evilic0.app"));
+        assert!(!allows("https://ic0.app.evil.com"));
+        assert!(!allows("http://localhost:8000"));
+    }
+
+    #[tokio::test]
+    async fn test_send() {
+        let (addr, rx) = serve(Some(
+            "HTTP/1.1 201 Created\r\nx-test: yes\r\ncontent-length: 5\r\n\r\nhello".into(),
+        ))
+        .await;
+
+        let res = client(&["127.0.0.1"], Duration::from_secs(5))
+            .send(Request {
+                method: "post".into(),
+                url: format!("http://{addr}/path"),
+                headers: vec![("x-custom".into(), "1".into())],
+                body: Some(b"ping".to_vec()),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(res.status, 201);
+        assert!(res.headers.contains(&("x-test".into(), "yes".into())));
+        assert_eq!(res.body, b"hello");
+
+        let req = rx.await.unwrap();
+        assert!(req.starts_with("POST /path HTTP/1.1"));
+        assert!(req.contains("x-custom: 1"));
+        assert!(req.ends_with("ping"));
+    }
+
+    #[tokio::test]
+    async fn test_host_not_allowed() {
+        let (addr, _rx) = serve(Some("HTTP/1.1 200 OK\r\n\r\n".into())).await;
+
+        let out = client(&["example.com"], Duration::from_secs(5))
+            .send(Request {
+                method: "GET".into(),
+                url: format!("http://{addr}"),
+                ..Default::default()
+            })
+            .await;
+
+        assert!(matches!(out, Err(HttpError::HostNotAllowed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_redirect_not_allowed() {
+        let (addr, _rx) = serve(Some(
+            "HTTP/1.1 302 Found\r\nlocation: http://example.com/\r\ncontent-length: 0\r\n\r\n"
+                .into(),
+        ))
+        .await;
+
+        let out = client(&["127.0.0.1"], Duration::from_secs(5))
+            .send(Request {
+                method: "GET".into(),
+                url: format!("http://{addr}"),
+                ..Default::default()
+            })
+            .await;
+
+        assert!(
+            matches!(&out, Err(HttpError::HostNotAllowed(h)) if h == "example.com:80"),
+            "{out:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let (addr, _rx) = serve(None).await;
+
+        let out = client(&["127.0.0.1"], Duration::from_millis(200))
+            .send(Request {
+                method: "GET".into(),
+                url: format!("http://{addr}"),
+                ..Default::default()
+            })
+            .await;
+
+        assert!(matches!(out, Err(HttpError::Timeout(_))), "{out:?}");
+    }
+}
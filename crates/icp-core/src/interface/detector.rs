@@ -3,6 +3,7 @@
 //! This module provides functionality for detecting interfaces in WebAssembly components
 //! by inspecting their structure using the wasmtime API.
 
+use super::is_host_interface;
 use crate::error::Error;
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -24,9 +25,12 @@ pub struct Interface {
 /// Represents all interfaces of a WebAssembly component
 #[derive(Debug, PartialEq, Clone)]
 pub struct ComponentInterfaces {
-    /// Interfaces imported by the component
+    /// Interfaces imported by the component from other extensions
     pub imports: Vec<Interface>,
 
+    /// Interfaces imported by the component from the host (e.g. "icp:cli/misc", "wasi:cli/stdout")
+    pub host_imports: Vec<Interface>,
+
     /// Interfaces exported by the component
     pub exports: Vec<Interface>,
 }
@@ -78,6 +82,7 @@ impl DetectIfaces for IfaceDetector {
 
         // imports
         let mut imports: Vec<Interface> = Vec::new();
+        let mut host_imports: Vec<Interface> = Vec::new();
 
         for imp in typ.imports(engine) {
             let (name, item) = imp;
@@ -124,10 +129,16 @@ impl DetectIfaces for IfaceDetector {
                 import_names.insert(iface_name.clone(), 1);
             }
 
-            imports.push(Interface {
+            let iface = Interface {
                 name: iface_name,
                 funcs,
-            });
+            };
+
+            // Host interfaces are linked by the host, not by other extensions
+            match is_host_interface(&iface.name) {
+                true => host_imports.push(iface),
+                false => imports.push(iface),
+            }
         }
 
         // exports
@@ -203,7 +214,11 @@ impl DetectIfaces for IfaceDetector {
             }
         }
 
-        Ok(ComponentInterfaces { imports, exports })
+        Ok(ComponentInterfaces {
+            imports,
+            host_imports,
+            exports,
+        })
     }
 }
 
@@ -296,6 +311,35 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_host_imports_detection() -> Result<(), anyhow::Error> {
+        let engine = create_test_engine()?;
+        let detector = IfaceDetector;
+
+        let wat = r#"
+        (component
+          (import "icp:cli/misc@0.4.0" (instance (export "print" (func))))
+          (import "wasi:cli/environment@0.2.0" (instance (export "get-arguments" (func))))
+          (import "icp:math/lib@1.0.0" (instance (export "add" (func))))
+        )
+        "#;
+
+        let component = Component::new(&engine, wat)?;
+        let interfaces = detector.detect(&engine, &component).await?;
+
+        let names = |ifaces: &[Interface]| -> Vec<String> {
+            ifaces.iter().map(|i| i.name.clone()).collect()
+        };
+
+        assert_eq!(names(&interfaces.imports), vec!["icp:math/lib@1.0.0"]);
+        assert_eq!(
+            names(&interfaces.host_imports),
+            vec!["icp:cli/misc@0.4.0", "wasi:cli/environment@0.2.0"]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_interface_creation() {
         let interface = Interface {
@@ -344,6 +388,7 @@ mod tests {
 
         let component_interfaces = ComponentInterfaces {
             imports: imports.clone(),
+            host_imports: vec![],
             exports: exports.clone(),
         };
 
@@ -355,6 +400,7 @@ mod tests {
     fn test_component_interfaces_empty() {
         let component_interfaces = ComponentInterfaces {
             imports: vec![],
+            host_imports: vec![],
             exports: vec![],
         };
 
@@ -382,6 +428,7 @@ mod tests {
 
         let component_interfaces = ComponentInterfaces {
             imports: imports.clone(),
+            host_imports: vec![],
             exports: exports.clone(),
         };
 
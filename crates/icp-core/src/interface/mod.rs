@@ -62,6 +62,10 @@
 //! - **Host-provided interfaces:** Extensions can import interfaces provided by the host CLI application
 //!   itself (e.g., "icp:cli/misc", "icp:cli/filesystem"). These are identified by the
 //!   [`HOST_INTERFACE_PREFIX`] and are linked directly by the host.
+//! - **WASI interfaces:** Extensions built against WASI Preview 2 import its interfaces (e.g.,
+//!   "wasi:cli/stdout"), identified by the [`WASI_INTERFACE_PREFIX`]. Like host-provided interfaces,
+//!   they are never provided by other extensions, so [`IfaceDetector`] reports both separately
+//!   (see [`ComponentInterfaces::host_imports`]).
 //! - **CLI command definition:** Extensions export a specific interface (typically `icp:cli/cli`)
 //!   that the host uses to integrate the extension's commands.
 //!
@@ -88,6 +92,74 @@ pub use detector::{ComponentInterfaces, DetectIfaces, IfaceDetector, Interface};
 /// during dependency validation and linking compared to inter-extension interfaces.
 pub const HOST_INTERFACE_PREFIX: &str = "icp:cli/";
 
+/// Version of the `icp:cli` package implemented by the host.
+pub const HOST_INTERFACE_VERSION: &str = "0.5.0";
+
+/// Prefix used to identify WASI interfaces (e.g., "wasi:cli/stdout@0.2.0").
+///
+/// These interfaces are provided by the host when WASI is enabled for an extension.
+pub const WASI_INTERFACE_PREFIX: &str = "wasi:";
+
+/// Check whether an interface is provided by the host (including WASI interfaces),
+/// as opposed to being provided by another extension
+///
+/// # Examples
+///
+/// ```
+/// use icp_core::interface::is_host_interface;
+///
+/// assert!(is_host_interface("icp:cli/misc@0.4.0"));
+/// assert!(is_host_interface("wasi:cli/stdout@0.2.0"));
+/// assert!(!is_host_interface("math/lib@1.0.0"));
+/// ```
+pub fn is_host_interface(name: &str) -> bool {
+    let (base_name, _) = parse_interface_name(name);
+
+    base_name.starts_with(HOST_INTERFACE_PREFIX) || base_name.starts_with(WASI_INTERFACE_PREFIX)
+}
+
+/// Find the version of the `icp:cli` package a component was built for, given the names of the
+/// interfaces it imports and exports, if it is not compatible with [`HOST_INTERFACE_VERSION`]
+///
+/// Versions are compatible if they have the same major version, or the same minor version
+/// for `0.x` versions. The incompatible version is returned without its patch (e.g. `0.3`).
+///
+/// # Examples
+///
+/// ```
+/// use icp_core::interface::incompatible_host_version;
+///
+/// let names = ["icp:cli/misc@0.3.4", "math/lib@1.0.0"];
+/// assert_eq!(incompatible_host_version(names), Some("0.3".to_string()));
+///
+/// let names = ["icp:cli/misc@0.5.0", "wasi:cli/stdout@0.2.0"];
+/// assert_eq!(incompatible_host_version(names), None);
+/// ```
+pub fn incompatible_host_version<'a>(names: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let host = compatibility_key(HOST_INTERFACE_VERSION);
+
+    names.into_iter().find_map(|name| {
+        let (base_name, version) = parse_interface_name(name);
+
+        if !base_name.starts_with(HOST_INTERFACE_PREFIX) {
+            return None;
+        }
+
+        let version = compatibility_key(&version?);
+        (version != host).then_some(version)
+    })
+}
+
+/// Part of a version that compatible versions share (e.g. `0.5` for `0.5.2`, `1` for `1.2.0`)
+fn compatibility_key(version: &str) -> String {
+    let mut parts = version.split('.');
+
+    match (parts.next(), parts.next()) {
+        (Some("0"), Some(minor)) => format!("0.{minor}"),
+        (major, _) => major.unwrap_or_default().to_owned(),
+    }
+}
+
 /// Parse an interface name into its base name and version components
 ///
 /// This function extracts the base name and version from an interface name.
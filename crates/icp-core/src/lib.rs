@@ -6,15 +6,33 @@
 pub mod component;
 pub mod dependency;
 mod error;
+pub mod http;
 pub mod interface;
 pub mod manifest;
+pub mod metadata;
+pub mod output;
+pub mod random;
+pub mod sandbox;
+pub mod spec;
+pub mod storage;
 
 // Re-export core types and traits
-pub use component::{DynamicLinker, DynamicLinkingError, FunctionRegistry, FunctionRegistryError};
-pub use dependency::{DependencyError, DependencyGraph};
+pub use component::{
+    CallTracker, DynamicLinker, DynamicLinkingError, FunctionRegistry, FunctionRegistryError,
+};
+pub use dependency::{DependencyEdge, DependencyError, DependencyGraph, InterfaceChange};
 pub use error::Error;
+pub use http::{Allowlist, HttpClient, HttpError};
 pub use interface::{ComponentInterfaces, DetectIfaces};
-pub use manifest::{Extension, Interface, Load, LoadError, Manifest, ManifestHandle, Store};
+pub use manifest::{
+    Extension, Interface, Limits, Load, LoadError, Manifest, ManifestHandle, Permissions, Store,
+};
+pub use metadata::{Metadata, MetadataError};
+pub use output::{OutputError, OutputFormat};
+pub use random::{Random, RandomError};
+pub use sandbox::{Sandbox, SandboxError};
+pub use spec::{ArgSpec, CommandSpec, SpecError};
+pub use storage::{Storage, StorageError};
 
 /// Version of the icp-core crate
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
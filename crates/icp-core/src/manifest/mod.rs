@@ -3,5 +3,6 @@
 mod model;
 
 pub use model::{
-    Extension, Interface, Load, LoadError, Manifest, ManifestHandle, Store, StoreError,
+    Extension, Interface, Limits, Load, LoadError, Manifest, ManifestHandle, Permissions, Store,
+    StoreError,
 };
@@ -1,15 +1,18 @@
 //! Data model for icp extension manifests.
 
 use std::{
-    fs::{create_dir_all, read, write},
+    fs::{create_dir_all, read, rename, write},
     io::ErrorKind,
     path::PathBuf,
+    time::Duration,
 };
 
 use anyhow::{anyhow, Context as _};
 use serde::{Deserialize, Serialize};
 use serde_json::{from_slice, to_vec_pretty};
 
+use crate::{metadata::Metadata, spec::CommandSpec};
+
 /// Represents an interface exported by an extension
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Interface {
@@ -20,6 +23,109 @@ pub struct Interface {
     pub funcs: Vec<String>,
 }
 
+/// Host capabilities granted to an extension beyond its defaults
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Permissions {
+    /// Directories outside of the workspace root the extension may access
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub directories: Vec<PathBuf>,
+
+    /// Commands the extension may execute on the host
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<String>,
+
+    /// Environment variables the extension may read, either names or prefixes ending in `*`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<String>,
+
+    /// Hosts the extension may send HTTP requests to (`host` or `host:port`, optionally `*.domain`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hosts: Vec<String>,
+}
+
+impl Permissions {
+    /// Check if no permissions are granted
+    pub fn is_empty(&self) -> bool {
+        self.directories.is_empty()
+            && self.commands.is_empty()
+            && self.variables.is_empty()
+            && self.hosts.is_empty()
+    }
+
+    /// Keep the granted permissions, taking each kind that is not granted from `previous`
+    pub fn or(self, previous: &Permissions) -> Permissions {
+        fn or<T: Clone>(v: Vec<T>, previous: &[T]) -> Vec<T> {
+            if v.is_empty() {
+                previous.to_vec()
+            } else {
+                v
+            }
+        }
+
+        Permissions {
+            directories: or(self.directories, &previous.directories),
+            commands: or(self.commands, &previous.commands),
+            variables: or(self.variables, &previous.variables),
+            hosts: or(self.hosts, &previous.hosts),
+        }
+    }
+
+    /// Check if the extension may read the given environment variable
+    pub fn allows_variable(&self, name: &str) -> bool {
+        self.variables.iter().any(|v| match v.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => v == name,
+        })
+    }
+}
+
+/// Resource limits applied to an extension, defaulting to [`Limits::DEFAULT_MEMORY`] and [`Limits::DEFAULT_FUEL`]
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Limits {
+    /// Maximum size of each of the extension's memories, in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<u64>,
+
+    /// Fuel available to each call into the extension (roughly, the number of instructions it may execute)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuel: Option<u64>,
+
+    /// Time allowed for each HTTP request sent by the extension, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_timeout: Option<u64>,
+}
+
+impl Limits {
+    /// Default memory limit (1 GiB)
+    pub const DEFAULT_MEMORY: u64 = 1 << 30;
+
+    /// Default fuel limit (a few seconds of execution)
+    pub const DEFAULT_FUEL: u64 = 10_000_000_000;
+
+    /// Default HTTP request timeout, in seconds
+    pub const DEFAULT_HTTP_TIMEOUT: u64 = 30;
+
+    /// Maximum size of each of the extension's memories, in bytes
+    pub fn memory(&self) -> u64 {
+        self.memory.unwrap_or(Self::DEFAULT_MEMORY)
+    }
+
+    /// Fuel available to each call into the extension
+    pub fn fuel(&self) -> u64 {
+        self.fuel.unwrap_or(Self::DEFAULT_FUEL)
+    }
+
+    /// Time allowed for each HTTP request sent by the extension
+    pub fn http_timeout(&self) -> Duration {
+        Duration::from_secs(self.http_timeout.unwrap_or(Self::DEFAULT_HTTP_TIMEOUT))
+    }
+
+    /// Check if only the defaults apply
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
 /// Represents an extension in the manifest
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Extension {
@@ -43,6 +149,42 @@ pub struct Extension {
     /// Expected SHA256 checksum for verification
     #[serde(skip_serializing_if = "Option::is_none")]
     pub checksum: Option<String>,
+
+    /// Compatibility hash of the engine that produced the pre-compiled component
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compatibility_hash: Option<String>,
+
+    /// Host capabilities granted to this extension
+    #[serde(default, skip_serializing_if = "Permissions::is_empty")]
+    pub permissions: Permissions,
+
+    /// Resource limits applied to this extension
+    #[serde(default, skip_serializing_if = "Limits::is_default")]
+    pub limits: Limits,
+
+    /// Whether the extension is linked against WASI Preview 2 (set when it imports `wasi:*` interfaces)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub wasi: bool,
+
+    /// Command specification reported by the extension, validated and cached at install time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spec: Option<CommandSpec>,
+
+    /// Descriptive metadata of the extension, read from the component at install time
+    #[serde(default, skip_serializing_if = "Metadata::is_empty")]
+    pub metadata: Metadata,
+
+    /// Local path or URI the extension was installed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Registry index the extension was installed from, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+
+    /// Time at which the extension was installed, in RFC 3339 format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_at: Option<String>,
 }
 
 /// Represents the manifest of installed extensions
@@ -116,12 +258,56 @@ impl Store for ManifestHandle {
 
         create_dir_all(md).context("failed to create manifest directory")?;
 
+        // Write to a temporary file first, so the manifest is replaced atomically
+        let tmp = self.0.with_extension("json.tmp");
+
         write(
-            &self.0, // path
-            bs,      // content
+            &tmp, // path
+            bs,   // content
         )
         .context("failed to write manifest")?;
 
+        rename(&tmp, &self.0).context("failed to replace manifest")?;
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows_variable() {
+        let p = Permissions {
+            variables: vec!["DFX_NETWORK".to_string(), "ICP_*".to_string()],
+            ..Default::default()
+        };
+
+        assert!(p.allows_variable("DFX_NETWORK"));
+        assert!(p.allows_variable("ICP_IDENTITY"));
+        assert!(!p.allows_variable("DFX_NETWORK_2"));
+        assert!(!p.allows_variable("HOME"));
+    }
+
+    #[test]
+    fn test_permissions_or() {
+        let previous = Permissions {
+            directories: vec![PathBuf::from("/data")],
+            commands: vec!["cargo".to_string()],
+            variables: vec!["ICP_*".to_string()],
+            hosts: vec!["ic0.app".to_string()],
+        };
+
+        let p = Permissions {
+            hosts: vec!["localhost:4943".to_string()],
+            ..Default::default()
+        }
+        .or(&previous);
+
+        assert_eq!(p.directories, previous.directories);
+        assert_eq!(p.commands, previous.commands);
+        assert_eq!(p.variables, previous.variables);
+        assert_eq!(p.hosts, vec!["localhost:4943".to_string()]);
+    }
+}
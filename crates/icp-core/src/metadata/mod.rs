@@ -0,0 +1,215 @@
+//! Descriptive metadata of icp extensions.
+//!
+//! Components can carry metadata in custom sections, as added by
+//! `wasm-tools metadata add` (e.g. `--version 1.2.0 --source https://github.com/org/ext`):
+//!
+//! * `version`: version of the extension
+//! * `description`: human-readable description
+//! * `authors`: comma-separated list of authors
+//! * `source`: URL of the extension's source repository
+//!
+//! Only the sections of the component itself are read, not those of the modules or
+//! components nested in it. Extensions without such sections fall back to the `version`
+//! and `help` of their command spec.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use wasmparser::{Parser, Payload};
+
+use crate::spec::CommandSpec;
+
+/// Errors that can occur while reading metadata
+#[derive(Debug, Error)]
+pub enum MetadataError {
+    /// The component could not be parsed
+    #[error("invalid component: {0}")]
+    InvalidComponent(String),
+
+    /// A metadata section is not valid UTF-8
+    #[error("invalid '{0}' section: not valid UTF-8")]
+    InvalidSection(String),
+}
+
+/// Descriptive metadata of an extension
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Metadata {
+    /// Version of the extension
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Human-readable description of the extension
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Authors of the extension
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+
+    /// URL of the extension's source repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+}
+
+impl Metadata {
+    /// Reads the metadata of a component from its custom sections
+    pub fn read(bs: &[u8]) -> Result<Self, MetadataError> {
+        let mut md = Self::default();
+
+        // Nesting level of the module or component being parsed (1 for the component itself)
+        let mut depth = 0;
+
+        for payload in Parser::new(0).parse_all(bs) {
+            let payload =
+                payload.map_err(|err| MetadataError::InvalidComponent(err.to_string()))?;
+
+            match payload {
+                Payload::Version { .. } => depth += 1,
+                Payload::End(_) => depth -= 1,
+
+                Payload::CustomSection(r) if depth == 1 => {
+                    let field = match r.name() {
+                        "version" => &mut md.version,
+                        "description" => &mut md.description,
+                        "source" => &mut md.repository,
+                        "authors" => {
+                            md.authors = text(r.name(), r.data())?
+                                .split(',')
+                                .map(str::trim)
+                                .filter(|a| !a.is_empty())
+                                .map(str::to_owned)
+                                .collect();
+
+                            continue;
+                        }
+                        _ => continue,
+                    };
+
+                    *field = Some(text(r.name(), r.data())?.trim().to_owned());
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(md)
+    }
+
+    /// Fills in the version and description missing from the metadata with those of a command spec
+    pub fn or_spec(self, spec: &CommandSpec) -> Self {
+        Self {
+            version: self.version.or_else(|| spec.version.clone()),
+            description: self.description.or_else(|| spec.help.clone()),
+            ..self
+        }
+    }
+
+    /// Check if no metadata is known
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+fn text<'a>(name: &str, data: &'a [u8]) -> Result<&'a str, MetadataError> {
+    std::str::from_utf8(data).map_err(|_| MetadataError::InvalidSection(name.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Header of a component
+    const COMPONENT: [u8; 8] = [0x00, 0x61, 0x73, 0x6d, 0x0d, 0x00, 0x01, 0x00];
+
+    /// Header of a core module
+    const MODULE: [u8; 8] = [0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00];
+
+    fn section(id: u8, content: &[u8]) -> Vec<u8> {
+        assert!(content.len() < 128);
+
+        let mut bs = vec![id, content.len() as u8];
+        bs.extend_from_slice(content);
+        bs
+    }
+
+    fn custom(name: &str, data: &str) -> Vec<u8> {
+        let mut content = vec![name.len() as u8];
+        content.extend_from_slice(name.as_bytes());
+        content.extend_from_slice(data.as_bytes());
+
+        section(0, &content)
+    }
+
+    #[test]
+    fn test_read() {
+        let bs = [
+            COMPONENT.to_vec(),
+            custom("version", "1.2.0"),
+            custom("description", "Build canisters"),
+            custom("authors", "Jane <jane@example.com>, John"),
+            custom("source", "https://github.com/org/build"),
+            custom("producers", "ignored"),
+        ]
+        .concat();
+
+        assert_eq!(
+            Metadata::read(&bs).unwrap(),
+            Metadata {
+                version: Some("1.2.0".to_string()),
+                description: Some("Build canisters".to_string()),
+                authors: vec!["Jane <jane@example.com>".to_string(), "John".to_string()],
+                repository: Some("https://github.com/org/build".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_ignores_nested_sections() {
+        let module = [MODULE.to_vec(), custom("version", "0.0.1")].concat();
+
+        let bs = [
+            COMPONENT.to_vec(),
+            section(1, &module), // core module
+            custom("description", "outer"),
+        ]
+        .concat();
+
+        let md = Metadata::read(&bs).unwrap();
+
+        assert_eq!(md.version, None);
+        assert_eq!(md.description, Some("outer".to_string()));
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        let bs = [COMPONENT.to_vec(), custom("version", "x")].concat();
+        let mut invalid = bs.clone();
+        *invalid.last_mut().unwrap() = 0xff;
+
+        assert!(matches!(
+            Metadata::read(&invalid),
+            Err(MetadataError::InvalidSection(name)) if name == "version"
+        ));
+
+        assert!(matches!(
+            Metadata::read(&bs[..6]),
+            Err(MetadataError::InvalidComponent(_))
+        ));
+    }
+
+    #[test]
+    fn test_or_spec() {
+        let spec =
+            CommandSpec::parse(r#"{ "name": "build", "help": "Build", "version": "0.3.0" }"#)
+                .unwrap();
+
+        let md = Metadata {
+            version: Some("1.0.0".to_string()),
+            ..Default::default()
+        }
+        .or_spec(&spec);
+
+        assert_eq!(md.version, Some("1.0.0".to_string()));
+        assert_eq!(md.description, Some("Build".to_string()));
+        assert!(Metadata::default().is_empty());
+    }
+}
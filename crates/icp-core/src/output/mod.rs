@@ -0,0 +1,226 @@
+//! Structured results of icp extension commands.
+//!
+//! Extensions emit the results of their commands as JSON values, which the host writes to
+//! stdout in the format chosen with `--output`: as JSON, for scripts, or rendered as text,
+//! for humans. Text rendering follows the shape of the value:
+//!
+//! * Scalars are written as they are (strings without quotes), and `null` is omitted
+//! * Arrays of objects are written as a table, with a column for each key
+//! * Other arrays are written one item per line
+//! * Objects are written as aligned `key: value` lines, in order of appearance
+//!
+//! Values nested deeper than that are written as compact JSON.
+
+use std::{fmt, iter::once, str::FromStr};
+
+use serde_json::Value;
+use thiserror::Error;
+
+/// Errors that can occur while handling a result
+#[derive(Debug, Error)]
+pub enum OutputError {
+    /// The output format is not known
+    #[error("unknown output format '{0}' (expected 'text' or 'json')")]
+    UnknownFormat(String),
+
+    /// The result is not valid JSON
+    #[error("invalid result: {0}")]
+    InvalidResult(String),
+}
+
+/// Format in which results are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+
+    /// JSON, one document per result
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(OutputError::UnknownFormat(s.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Text => "text",
+            Self::Json => "json",
+        })
+    }
+}
+
+/// Parses a result emitted by an extension
+pub fn parse(s: &str) -> Result<Value, OutputError> {
+    serde_json::from_str(s).map_err(|err| OutputError::InvalidResult(err.to_string()))
+}
+
+/// Renders a result in the given format, without a trailing newline
+///
+/// # Returns
+///
+/// The rendered result, which is empty for `null` in text format
+pub fn render(value: &Value, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(value).expect("failed to serialize JSON value")
+        }
+        OutputFormat::Text => text(value),
+    }
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::Array(items) if !items.is_empty() && items.iter().all(Value::is_object) => {
+            table(items)
+        }
+
+        Value::Array(items) => items.iter().map(scalar).collect::<Vec<_>>().join("\n"),
+
+        Value::Object(fields) => {
+            let w = fields.keys().map(|k| width(k)).max().unwrap_or(0) + 1;
+
+            fields
+                .iter()
+                .map(|(k, v)| format!("{:w$} {}", format!("{k}:"), scalar(v)))
+                .map(|line| line.trim_end().to_owned())
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+
+        v => scalar(v),
+    }
+}
+
+/// Renders an array of objects as a table, with a column for each key (in order of appearance)
+fn table(rows: &[Value]) -> String {
+    let mut columns: Vec<&str> = vec![];
+
+    for row in rows.iter().filter_map(Value::as_object) {
+        for k in row.keys() {
+            if !columns.contains(&k.as_str()) {
+                columns.push(k);
+            }
+        }
+    }
+
+    let header: Vec<String> = columns.iter().map(|c| c.to_uppercase()).collect();
+
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .filter_map(Value::as_object)
+        .map(|row| {
+            columns
+                .iter()
+                .map(|c| row.get(*c).map(scalar).unwrap_or_default())
+                .collect()
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|i| {
+            let cells = cells.iter().map(|r| width(&r[i]));
+            cells.fold(width(&header[i]), usize::max)
+        })
+        .collect();
+
+    once(&header)
+        .chain(cells.iter())
+        .map(|r| {
+            r.iter()
+                .zip(&widths)
+                .map(|(cell, w)| format!("{cell:w$}"))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_owned()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Width of a cell, in characters
+fn width(s: &str) -> usize {
+    s.chars().count()
+}
+
+/// Renders a value on a single line
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.to_owned(),
+        v => v.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(OutputFormat::default().to_string(), "text");
+        assert!(matches!(
+            "yaml".parse::<OutputFormat>(),
+            Err(OutputError::UnknownFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid_result() {
+        assert!(matches!(parse("{"), Err(OutputError::InvalidResult(_))));
+    }
+
+    #[test]
+    fn test_render_json() {
+        let v = json!({ "name": "c1" });
+        assert_eq!(render(&v, OutputFormat::Json), "{\n  \"name\": \"c1\"\n}");
+    }
+
+    #[test]
+    fn test_render_text_scalars_and_lists() {
+        assert_eq!(render(&json!("done"), OutputFormat::Text), "done");
+        assert_eq!(render(&json!(42), OutputFormat::Text), "42");
+        assert_eq!(render(&Value::Null, OutputFormat::Text), "");
+        assert_eq!(
+            render(&json!(["a", 1, [2]]), OutputFormat::Text),
+            "a\n1\n[2]"
+        );
+    }
+
+    #[test]
+    fn test_render_text_object() {
+        let v = json!({ "name": "c1", "canister_type": "motoko", "deps": ["a"] });
+
+        assert_eq!(
+            render(&v, OutputFormat::Text),
+            "name:          c1\ncanister_type: motoko\ndeps:          [\"a\"]"
+        );
+    }
+
+    #[test]
+    fn test_render_text_table() {
+        let v = json!([
+            { "name": "backend", "type": "rust" },
+            { "name": "ui", "type": "assets", "path": "src/ui" },
+        ]);
+
+        assert_eq!(
+            render(&v, OutputFormat::Text),
+            "NAME     TYPE    PATH\nbackend  rust\nui       assets  src/ui"
+        );
+    }
+}
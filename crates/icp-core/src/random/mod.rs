@@ -0,0 +1,131 @@
+//! Random number generation for icp extensions.
+//!
+//! Randomness is drawn from the operating system's CSPRNG. For reproducible tests, a
+//! [`Random`] can instead be seeded, in which case it produces a deterministic stream
+//! derived from the seed and a stream name (e.g. the extension name), so that extensions
+//! get independent streams regardless of the order in which they are loaded.
+
+use ring::{
+    digest::{digest, SHA256},
+    rand::{SecureRandom, SystemRandom},
+};
+use thiserror::Error;
+
+/// Errors that can occur while generating random bytes
+#[derive(Debug, Error)]
+pub enum RandomError {
+    /// The operating system's random number generator failed
+    #[error("failed to generate random bytes")]
+    Unavailable,
+}
+
+/// Source of random bytes
+pub enum Random {
+    /// The operating system's CSPRNG
+    System(SystemRandom),
+
+    /// A deterministic stream, for testing
+    Seeded {
+        /// Key of the stream, derived from the seed and the stream name
+        key: [u8; 32],
+
+        /// Index of the next block in the stream
+        counter: u64,
+
+        /// Unused bytes of the current block
+        buf: Vec<u8>,
+    },
+}
+
+impl Random {
+    /// Create a source backed by the operating system's CSPRNG
+    pub fn system() -> Self {
+        Self::System(SystemRandom::new())
+    }
+
+    /// Create a deterministic source
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed shared by all streams
+    /// * `stream` - Name of the stream, so that different consumers get different bytes
+    pub fn seeded(seed: &str, stream: &str) -> Self {
+        let mut k = seed.as_bytes().to_vec();
+        k.push(0);
+        k.extend_from_slice(stream.as_bytes());
+
+        let mut key = [0; 32];
+        key.copy_from_slice(digest(&SHA256, &k).as_ref());
+
+        Self::Seeded {
+            key,
+            counter: 0,
+            buf: vec![],
+        }
+    }
+
+    /// Fill a buffer with random bytes
+    pub fn fill(&mut self, out: &mut [u8]) -> Result<(), RandomError> {
+        match self {
+            Self::System(rng) => rng.fill(out).map_err(|_| RandomError::Unavailable),
+
+            Self::Seeded { key, counter, buf } => {
+                for b in out.iter_mut() {
+                    if buf.is_empty() {
+                        let mut block = key.to_vec();
+                        block.extend_from_slice(&counter.to_be_bytes());
+
+                        *buf = digest(&SHA256, &block).as_ref().to_vec();
+                        *counter += 1;
+                    }
+
+                    *b = buf.remove(0);
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(rng: &mut Random, len: usize) -> Vec<u8> {
+        let mut out = vec![0; len];
+        rng.fill(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn test_seeded_is_deterministic() {
+        let mut a = Random::seeded("seed", "identity");
+        let mut b = Random::seeded("seed", "identity");
+
+        // Reading in different chunk sizes yields the same stream
+        let mut out = bytes(&mut a, 5);
+        out.extend(bytes(&mut a, 60));
+
+        assert_eq!(out, bytes(&mut b, 65));
+    }
+
+    #[test]
+    fn test_seeded_streams_are_independent() {
+        let mut a = Random::seeded("seed", "identity");
+        let mut b = Random::seeded("seed", "project");
+        let mut c = Random::seeded("other", "identity");
+
+        let out = bytes(&mut a, 32);
+
+        assert_ne!(out, bytes(&mut b, 32));
+        assert_ne!(out, bytes(&mut c, 32));
+    }
+
+    #[test]
+    fn test_system() {
+        let mut rng = Random::system();
+
+        assert_ne!(bytes(&mut rng, 32), bytes(&mut rng, 32));
+    }
+}
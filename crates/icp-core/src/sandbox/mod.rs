@@ -0,0 +1,444 @@
+//! Filesystem sandboxing for icp extensions.
+//!
+//! Extensions access the filesystem through the host, which confines them to the
+//! workspace root and to the directories granted to them in the manifest. Paths are
+//! resolved against the current directory and symlinks are followed before checking
+//! them, so neither `..` components nor links can be used to escape the sandbox.
+
+use std::{
+    io,
+    path::{Component, Path, PathBuf},
+};
+
+use thiserror::Error;
+
+/// Name of the project manifest that marks the root of a workspace
+pub const WORKSPACE_MANIFEST: &str = "icp.toml";
+
+/// Errors that can occur while resolving a path in the sandbox
+#[derive(Debug, Error)]
+pub enum SandboxError {
+    /// The path resolves outside of the allowed directories
+    #[error("access denied: {0} is outside of the allowed directories")]
+    AccessDenied(PathBuf),
+
+    /// The path could not be resolved
+    #[error("failed to resolve path {path}: {err}")]
+    Io {
+        /// The path being resolved
+        path: PathBuf,
+
+        /// The underlying error
+        #[source]
+        err: io::Error,
+    },
+
+    /// The glob pattern is malformed
+    #[error("invalid pattern '{pattern}': {reason}")]
+    InvalidPattern {
+        /// The pattern being matched
+        pattern: String,
+
+        /// Why the pattern is invalid
+        reason: String,
+    },
+}
+
+/// Finds the workspace root for the given directory.
+///
+/// The workspace root is the closest ancestor (including the directory itself) that
+/// contains a [`WORKSPACE_MANIFEST`]. If there is none, the directory itself is used.
+pub fn find_workspace_root(dir: &Path) -> PathBuf {
+    dir.ancestors()
+        .find(|p| p.join(WORKSPACE_MANIFEST).is_file())
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+/// Confines filesystem access to a set of root directories
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    /// Directory relative paths are resolved against
+    cwd: PathBuf,
+
+    /// Directories (canonicalized, where they exist) access is allowed to
+    roots: Vec<PathBuf>,
+}
+
+impl Sandbox {
+    /// Create a new sandbox
+    ///
+    /// # Arguments
+    ///
+    /// * `cwd` - Directory relative paths are resolved against
+    /// * `roots` - Directories access is allowed to, relative ones being resolved against `cwd`
+    pub fn new(cwd: &Path, roots: impl IntoIterator<Item = PathBuf>) -> Self {
+        let cwd = cwd.canonicalize().unwrap_or_else(|_| cwd.to_path_buf());
+
+        let roots = roots
+            .into_iter()
+            .map(|r| {
+                let r = cwd.join(r);
+                r.canonicalize().unwrap_or(r)
+            })
+            .collect();
+
+        Self { cwd, roots }
+    }
+
+    /// Directories access is allowed to
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    /// Resolve a path, following symlinks, and check that it lies within one of the roots
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to resolve, either absolute or relative to the current directory
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PathBuf)` with the resolved absolute path if access is allowed
+    /// * `Err(SandboxError::AccessDenied)` if the path resolves outside of the roots
+    pub fn resolve(&self, path: impl AsRef<Path>) -> Result<PathBuf, SandboxError> {
+        let p = self.cwd.join(path.as_ref());
+
+        // Find the longest prefix of the path that exists on disk and canonicalize it,
+        // which resolves any symlinks and `..` components within it
+        let mut tail = vec![];
+        let mut prefix = p.as_path();
+
+        let mut resolved = loop {
+            match prefix.canonicalize() {
+                Ok(canonical) => break canonical,
+
+                Err(err) => {
+                    // A dangling symlink cannot be checked, since writing to it would create its target
+                    if prefix.symlink_metadata().is_ok() {
+                        return Err(SandboxError::AccessDenied(p));
+                    }
+
+                    let (Some(parent), Some(name)) =
+                        (prefix.parent(), prefix.components().next_back())
+                    else {
+                        return Err(SandboxError::Io { path: p, err });
+                    };
+
+                    tail.push(name);
+                    prefix = parent;
+                }
+            }
+        };
+
+        // The remaining components do not exist, so they can be resolved lexically
+        for c in tail.into_iter().rev() {
+            match c {
+                Component::ParentDir => {
+                    resolved.pop();
+                }
+                Component::Normal(name) => resolved.push(name),
+                _ => {}
+            }
+        }
+
+        if !self.roots.iter().any(|r| resolved.starts_with(r)) {
+            return Err(SandboxError::AccessDenied(resolved));
+        }
+
+        Ok(resolved)
+    }
+
+    /// Resolve a path without following a symlink at its final component, and check that the
+    /// entry it names lies within one of the roots
+    ///
+    /// Only the parent directory is resolved (following symlinks), so the result designates the
+    /// entry itself, be it a symlink, rather than what it points to. This is the path to operate on
+    /// when removing or renaming entries, which must affect a link and not its target.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to resolve, either absolute or relative to the current directory
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PathBuf)` with the resolved parent joined with the final component if access is allowed
+    /// * `Err(SandboxError::AccessDenied)` if the entry lies outside of the roots
+    pub fn resolve_entry(&self, path: impl AsRef<Path>) -> Result<PathBuf, SandboxError> {
+        let p = self.cwd.join(path.as_ref());
+
+        // Paths ending with `..` (or a root) don't name a link
+        let (Some(parent), Some(Component::Normal(name))) =
+            (p.parent(), p.components().next_back())
+        else {
+            return self.resolve(&p);
+        };
+
+        let resolved = self.resolve(parent)?.join(name);
+
+        if !self.roots.iter().any(|r| resolved.starts_with(r)) {
+            return Err(SandboxError::AccessDenied(resolved));
+        }
+
+        Ok(resolved)
+    }
+
+    /// Find the paths matching a glob pattern, omitting matches that resolve outside of the roots
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Glob pattern, either absolute or relative to the current directory
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<PathBuf>)` with the matches, sorted, and relative to the current directory if the pattern is
+    /// * `Err(SandboxError::AccessDenied)` if the literal part of the pattern lies outside of the roots
+    pub fn glob(&self, pattern: &str) -> Result<Vec<PathBuf>, SandboxError> {
+        let invalid = |reason: String| SandboxError::InvalidPattern {
+            pattern: pattern.to_owned(),
+            reason,
+        };
+
+        let p = self.cwd.join(pattern);
+
+        // The literal prefix of the pattern must be accessible, which also keeps the search
+        // from walking directories outside of the roots
+        let prefix: PathBuf = p
+            .components()
+            .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+            .collect();
+
+        self.resolve(&prefix)?;
+
+        let matches = glob::glob(&p.to_string_lossy()).map_err(|err| invalid(err.to_string()))?;
+
+        let mut out = vec![];
+
+        for m in matches {
+            let m = m.map_err(|err| SandboxError::Io {
+                path: err.path().to_path_buf(),
+                err: err.into_error(),
+            })?;
+
+            if self.resolve(&m).is_err() {
+                continue;
+            }
+
+            out.push(match m.strip_prefix(&self.cwd) {
+                Ok(rel) if Path::new(pattern).is_relative() => rel.to_path_buf(),
+                _ => m,
+            });
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir_all, write};
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn setup() -> (TempDir, PathBuf, PathBuf) {
+        let tmp = TempDir::new().unwrap();
+        let base = tmp.path().canonicalize().unwrap();
+
+        let ws = base.join("workspace");
+        let outside = base.join("outside");
+
+        create_dir_all(ws.join("src")).unwrap();
+        create_dir_all(&outside).unwrap();
+
+        write(ws.join(WORKSPACE_MANIFEST), "").unwrap();
+        write(outside.join("secret"), "").unwrap();
+
+        (tmp, ws, outside)
+    }
+
+    #[test]
+    fn test_find_workspace_root() {
+        let (_tmp, ws, outside) = setup();
+
+        assert_eq!(find_workspace_root(&ws.join("src")), ws);
+        assert_eq!(find_workspace_root(&ws), ws);
+        assert_eq!(find_workspace_root(&outside), outside);
+    }
+
+    #[test]
+    fn test_resolve_within_root() {
+        let (_tmp, ws, _) = setup();
+        let sb = Sandbox::new(&ws.join("src"), [ws.clone()]);
+
+        // Existing file
+        assert_eq!(
+            sb.resolve("../icp.toml").unwrap(),
+            ws.join(WORKSPACE_MANIFEST)
+        );
+
+        // New file in new directories
+        assert_eq!(
+            sb.resolve("a/b/../c.txt").unwrap(),
+            ws.join("src").join("a").join("c.txt")
+        );
+
+        // Absolute path
+        assert_eq!(sb.resolve(ws.join("src")).unwrap(), ws.join("src"));
+    }
+
+    #[test]
+    fn test_resolve_outside_root() {
+        let (_tmp, ws, outside) = setup();
+        let sb = Sandbox::new(&ws, [ws.clone()]);
+
+        assert!(matches!(
+            sb.resolve("../outside/secret"),
+            Err(SandboxError::AccessDenied(_))
+        ));
+
+        assert!(matches!(
+            sb.resolve(outside.join("secret")),
+            Err(SandboxError::AccessDenied(_))
+        ));
+
+        assert!(matches!(
+            sb.resolve("src/new/../../../outside/new"),
+            Err(SandboxError::AccessDenied(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_granted_directory() {
+        let (_tmp, ws, outside) = setup();
+        let sb = Sandbox::new(&ws, [ws.clone(), outside.clone()]);
+
+        assert_eq!(
+            sb.resolve("../outside/secret").unwrap(),
+            outside.join("secret")
+        );
+    }
+
+    #[test]
+    fn test_glob() {
+        let (_tmp, ws, outside) = setup();
+        let sb = Sandbox::new(&ws, [ws.clone()]);
+
+        write(ws.join("src").join("a.mo"), "").unwrap();
+        write(ws.join("src").join("b.mo"), "").unwrap();
+        write(ws.join("src").join("c.rs"), "").unwrap();
+
+        // Relative pattern
+        assert_eq!(
+            sb.glob("src/*.mo").unwrap(),
+            vec![PathBuf::from("src/a.mo"), PathBuf::from("src/b.mo")]
+        );
+
+        // Absolute pattern
+        assert_eq!(
+            sb.glob(&format!("{}/**/*.rs", ws.display())).unwrap(),
+            vec![ws.join("src").join("c.rs")]
+        );
+
+        // Outside of the roots
+        assert!(matches!(
+            sb.glob("../outside/*"),
+            Err(SandboxError::AccessDenied(_))
+        ));
+
+        assert!(matches!(
+            sb.glob(&format!("{}/*", outside.display())),
+            Err(SandboxError::AccessDenied(_))
+        ));
+
+        // Invalid pattern
+        assert!(matches!(
+            sb.glob("src/***"),
+            Err(SandboxError::InvalidPattern { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_symlink_escape() {
+        use std::os::unix::fs::symlink;
+
+        let (_tmp, ws, outside) = setup();
+        let sb = Sandbox::new(&ws, [ws.clone()]);
+
+        // Link to a directory outside of the workspace
+        symlink(&outside, ws.join("link")).unwrap();
+
+        assert!(matches!(
+            sb.resolve("link/secret"),
+            Err(SandboxError::AccessDenied(_))
+        ));
+
+        assert!(matches!(
+            sb.resolve("link/new"),
+            Err(SandboxError::AccessDenied(_))
+        ));
+
+        // Dangling link to a file outside of the workspace
+        symlink(outside.join("missing"), ws.join("dangling")).unwrap();
+
+        assert!(matches!(
+            sb.resolve("dangling"),
+            Err(SandboxError::AccessDenied(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_entry_symlink() {
+        use std::os::unix::fs::symlink;
+
+        let (_tmp, ws, outside) = setup();
+        let sb = Sandbox::new(&ws, [ws.clone()]);
+
+        // Links inside the workspace, to a file inside and to a file outside of it
+        symlink(ws.join(WORKSPACE_MANIFEST), ws.join("src").join("inside")).unwrap();
+        symlink(outside.join("secret"), ws.join("src").join("outside")).unwrap();
+
+        // The links themselves are designated, not their targets
+        assert_eq!(
+            sb.resolve_entry("src/inside").unwrap(),
+            ws.join("src").join("inside")
+        );
+
+        assert_eq!(
+            sb.resolve_entry("src/outside").unwrap(),
+            ws.join("src").join("outside")
+        );
+
+        assert!(matches!(
+            sb.resolve("src/outside"),
+            Err(SandboxError::AccessDenied(_))
+        ));
+
+        // Links in the parent are still followed
+        symlink(&outside, ws.join("link")).unwrap();
+
+        assert!(matches!(
+            sb.resolve_entry("link/secret"),
+            Err(SandboxError::AccessDenied(_))
+        ));
+
+        // Entries outside of the workspace, or the workspace itself
+        assert!(matches!(
+            sb.resolve_entry("../outside/secret"),
+            Err(SandboxError::AccessDenied(_))
+        ));
+
+        assert!(matches!(
+            sb.resolve_entry("src/.."),
+            Ok(p) if p == ws
+        ));
+
+        assert!(matches!(
+            sb.resolve_entry(&ws),
+            Err(SandboxError::AccessDenied(_))
+        ));
+    }
+}
@@ -0,0 +1,596 @@
+//! Command specifications reported by icp extensions.
+//!
+//! Extensions describe their command-line interface as a JSON [`CommandSpec`],
+//! returned by the `spec` function of the `icp:cli/cli` interface. The host
+//! validates the spec when an extension is added and caches it in the manifest,
+//! so the command tree can be assembled without instantiating any extension.
+//!
+//! Besides the short help shown in command listings, commands and arguments can have a
+//! long help, and commands can list usage examples. Both are shown by `--help` and in the
+//! reference docs generated by the host.
+//!
+//! Arguments can be boolean flags, take several values, be restricted to a set of values, have
+//! a default, or fall back to an environment variable. Arguments are parsed by the host, which
+//! passes their values to the extension's `run-parsed` function.
+//!
+//! The schema is versioned: the top-level spec declares the version it was written for in
+//! `schema` (version 1 when missing). Fields added since are optional, so older specs keep
+//! working, while specs written for a newer schema than the host supports are rejected.
+//!
+//! The top-level spec also lists the executables the extension needs to run through
+//! the `icp:cli/command` interface, which the user is asked to allow at install time,
+//! and the interfaces it registers with their providers through during `init` (e.g.
+//! `icp:build/registry` for build providers), so that it is loaded along with them.
+
+use std::{collections::HashSet, iter::once};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Latest version of the spec schema supported by the host
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Errors that can occur while parsing or validating a command specification
+#[derive(Debug, Error)]
+pub enum SpecError {
+    /// The specification is not valid JSON or does not match the schema
+    #[error("failed to parse command spec: {0}")]
+    Parse(String),
+
+    /// The spec was written for a newer schema than the host supports
+    #[error("command spec uses schema version {0}, but at most version {SCHEMA_VERSION} is supported (try updating icp)")]
+    UnsupportedSchema(u32),
+
+    /// A command or argument has an empty name
+    #[error("command '{0}' has an empty name")]
+    EmptyName(String),
+
+    /// An argument name is used more than once within a command
+    #[error("command '{command}' declares argument '{arg}' more than once")]
+    DuplicateArg {
+        /// Name of the command
+        command: String,
+
+        /// Name of the duplicated argument
+        arg: String,
+    },
+
+    /// A flag (short or long) is used more than once within a command
+    #[error("command '{command}' declares flag '{flag}' more than once")]
+    DuplicateFlag {
+        /// Name of the command
+        command: String,
+
+        /// The duplicated flag, including its dashes
+        flag: String,
+    },
+
+    /// A flag is reserved by the host
+    #[error("command '{command}' declares reserved flag '{flag}'")]
+    ReservedFlag {
+        /// Name of the command
+        command: String,
+
+        /// The reserved flag, including its dashes
+        flag: String,
+    },
+
+    /// An argument combines settings that cannot be used together
+    #[error("command '{command}' declares invalid argument '{arg}': {reason}")]
+    InvalidArg {
+        /// Name of the command
+        command: String,
+
+        /// Name of the argument
+        arg: String,
+
+        /// Why the argument is invalid
+        reason: String,
+    },
+
+    /// A requested command is empty or requested more than once
+    #[error("command '{command}' requests invalid or duplicate command '{executable}'")]
+    InvalidCommand {
+        /// Name of the command
+        command: String,
+
+        /// The requested command
+        executable: String,
+    },
+
+    /// A registration interface is empty, versioned or declared more than once
+    #[error(
+        "command '{command}' declares invalid or duplicate registration interface '{interface}'"
+    )]
+    InvalidRegistration {
+        /// Name of the command
+        command: String,
+
+        /// The declared interface
+        interface: String,
+    },
+
+    /// An example has an empty command line
+    #[error("command '{0}' declares an example with an empty command line")]
+    EmptyExample(String),
+
+    /// A subcommand name is used more than once within a command
+    #[error("command '{command}' declares subcommand '{subcommand}' more than once")]
+    DuplicateSubcommand {
+        /// Name of the command
+        command: String,
+
+        /// Name of the duplicated subcommand
+        subcommand: String,
+    },
+}
+
+/// Specification of a command, as reported by an extension
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CommandSpec {
+    /// Version of the spec schema (only meaningful for the top-level command, 1 when missing)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<u32>,
+
+    /// Name of the command
+    pub name: String,
+
+    /// Alternative names of the command
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+
+    /// Help text for the command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+
+    /// Detailed description of the command, shown by `--help` and in the reference docs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long_help: Option<String>,
+
+    /// Usage examples of the command
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<ExampleSpec>,
+
+    /// Version of the command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Whether the command is hidden from help and the reference docs
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+
+    /// Argument specification
+    #[serde(default)]
+    pub args: Vec<ArgSpec>,
+
+    /// Subcommands specification
+    #[serde(default)]
+    pub subcommands: Vec<CommandSpec>,
+
+    /// Commands the extension needs to execute on the host (only meaningful for the top-level command)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<String>,
+
+    /// Imported interfaces the extension registers with their provider through during `init`,
+    /// without version (e.g. `icp:build/registry`, only meaningful for the top-level command)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub registers: Vec<String>,
+}
+
+impl CommandSpec {
+    /// Checks whether the extension registers with the provider of an imported interface
+    /// (given with or without version)
+    pub fn registers_through(&self, interface: &str) -> bool {
+        let name = interface
+            .split_once('@')
+            .map_or(interface, |(name, _)| name);
+
+        self.registers.iter().any(|r| r == name)
+    }
+}
+
+/// Specification of a command argument
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ArgSpec {
+    /// Name of the argument
+    pub name: String,
+
+    /// Help text for the argument
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+
+    /// Detailed description of the argument, shown by `--help` and in the reference docs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long_help: Option<String>,
+
+    /// Short name for the argument
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short: Option<char>,
+
+    /// Long name for the argument
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long: Option<String>,
+
+    /// Alternative long names for the argument
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+
+    /// Whether argument is required
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    /// Whether the argument is a boolean flag, which takes no value
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub flag: bool,
+
+    /// Whether the argument can be given more than once (or take several values, if positional)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub multiple: bool,
+
+    /// Value used when the argument is not given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+
+    /// Values the argument is restricted to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub possible_values: Vec<String>,
+
+    /// Environment variable providing the value when the argument is not given
+    /// (only read if the extension is allowed to read the variable)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+
+    /// Whether the argument is hidden from help and the reference docs
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+}
+
+impl ArgSpec {
+    /// Whether the argument is positional, i.e. has neither a short nor a long name
+    pub fn is_positional(&self) -> bool {
+        self.short.is_none() && self.long.is_none()
+    }
+
+    /// Checks that the argument's settings can be used together
+    fn check(&self) -> Result<(), String> {
+        if self.flag && self.is_positional() {
+            return Err("flags need a short or long name".into());
+        }
+
+        if self.flag
+            && (self.multiple || self.default.is_some() || !self.possible_values.is_empty())
+        {
+            return Err("flags cannot take values".into());
+        }
+
+        if let Some(default) = &self.default {
+            if !self.possible_values.is_empty() && !self.possible_values.contains(default) {
+                return Err(format!("default value '{default}' is not a possible value"));
+            }
+        }
+
+        if !self.aliases.is_empty() && self.long.is_none() {
+            return Err("aliases need a long name".into());
+        }
+
+        Ok(())
+    }
+}
+
+/// A usage example of a command
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ExampleSpec {
+    /// Full command line, e.g. `icp project build`
+    pub command: String,
+
+    /// What the example does
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+}
+
+impl CommandSpec {
+    /// Parses and validates a command specification from its JSON representation
+    pub fn parse(s: &str) -> Result<Self, SpecError> {
+        let spec: Self =
+            serde_json::from_str(s).map_err(|err| SpecError::Parse(err.to_string()))?;
+
+        if let Some(v) = spec.schema.filter(|v| *v > SCHEMA_VERSION) {
+            return Err(SpecError::UnsupportedSchema(v));
+        }
+
+        spec.validate()?;
+
+        Ok(spec)
+    }
+
+    /// Validates the specification, including all of its subcommands
+    pub fn validate(&self) -> Result<(), SpecError> {
+        if self.name.trim().is_empty() {
+            return Err(SpecError::EmptyName(self.name.clone()));
+        }
+
+        let mut names = HashSet::new();
+        let mut flags = HashSet::new();
+
+        for arg in &self.args {
+            if arg.name.trim().is_empty() {
+                return Err(SpecError::EmptyName(self.name.clone()));
+            }
+
+            if !names.insert(arg.name.as_str()) {
+                return Err(SpecError::DuplicateArg {
+                    command: self.name.clone(),
+                    arg: arg.name.clone(),
+                });
+            }
+
+            arg.check().map_err(|reason| SpecError::InvalidArg {
+                command: self.name.clone(),
+                arg: arg.name.clone(),
+                reason,
+            })?;
+
+            let short = arg.short.map(|c| format!("-{c}"));
+            let long = arg.long.as_ref().map(|l| format!("--{l}"));
+            let aliases = arg.aliases.iter().map(|a| Some(format!("--{a}")));
+
+            for flag in [short, long].into_iter().chain(aliases).flatten() {
+                // Help flags are generated by the host
+                if flag == "-h" || flag == "--help" {
+                    return Err(SpecError::ReservedFlag {
+                        command: self.name.clone(),
+                        flag,
+                    });
+                }
+
+                if !flags.insert(flag.clone()) {
+                    return Err(SpecError::DuplicateFlag {
+                        command: self.name.clone(),
+                        flag,
+                    });
+                }
+            }
+        }
+
+        // Only the last positional argument can take several values
+        let positionals: Vec<_> = self.args.iter().filter(|a| a.is_positional()).collect();
+
+        if let Some((_, init)) = positionals.split_last() {
+            if let Some(arg) = init.iter().find(|a| a.multiple) {
+                return Err(SpecError::InvalidArg {
+                    command: self.name.clone(),
+                    arg: arg.name.clone(),
+                    reason: "only the last positional argument can take several values".into(),
+                });
+            }
+        }
+
+        let mut executables = HashSet::new();
+
+        for executable in &self.commands {
+            if executable.trim().is_empty() || !executables.insert(executable.as_str()) {
+                return Err(SpecError::InvalidCommand {
+                    command: self.name.clone(),
+                    executable: executable.clone(),
+                });
+            }
+        }
+
+        let mut registrations = HashSet::new();
+
+        for interface in &self.registers {
+            if interface.trim().is_empty()
+                || interface.contains('@')
+                || !registrations.insert(interface.as_str())
+            {
+                return Err(SpecError::InvalidRegistration {
+                    command: self.name.clone(),
+                    interface: interface.clone(),
+                });
+            }
+        }
+
+        if self.examples.iter().any(|e| e.command.trim().is_empty()) {
+            return Err(SpecError::EmptyExample(self.name.clone()));
+        }
+
+        let mut subcommands = HashSet::new();
+
+        for c in &self.subcommands {
+            for name in once(&c.name).chain(&c.aliases) {
+                if !subcommands.insert(name.as_str()) {
+                    return Err(SpecError::DuplicateSubcommand {
+                        command: self.name.clone(),
+                        subcommand: name.clone(),
+                    });
+                }
+            }
+
+            c.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_spec() {
+        let spec = CommandSpec::parse(
+            r#"{
+                "name": "project",
+                "help": "Internet Computer Project CLI",
+                "subcommands": [
+                    { "name": "create", "args": [{ "name": "name", "required": true }] },
+                    { "name": "build" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(spec.name, "project");
+        assert_eq!(spec.subcommands.len(), 2);
+        assert_eq!(spec.subcommands[0].args[0].required, Some(true));
+    }
+
+    #[test]
+    fn test_parse_malformed_spec() {
+        let err = CommandSpec::parse(r#"{ "help": "missing name" }"#).unwrap_err();
+        assert!(matches!(err, SpecError::Parse(_)));
+    }
+
+    #[test]
+    fn test_validate_duplicate_arg() {
+        let err =
+            CommandSpec::parse(r#"{ "name": "x", "args": [{ "name": "a" }, { "name": "a" }] }"#)
+                .unwrap_err();
+
+        assert!(matches!(err, SpecError::DuplicateArg { .. }));
+    }
+
+    #[test]
+    fn test_validate_duplicate_flag() {
+        let err = CommandSpec::parse(
+            r#"{ "name": "x", "args": [{ "name": "a", "short": "a" }, { "name": "b", "short": "a" }] }"#,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "command 'x' declares flag '-a' more than once"
+        );
+    }
+
+    #[test]
+    fn test_validate_reserved_flag() {
+        let err =
+            CommandSpec::parse(r#"{ "name": "x", "args": [{ "name": "a", "long": "help" }] }"#)
+                .unwrap_err();
+
+        assert!(matches!(err, SpecError::ReservedFlag { .. }));
+    }
+
+    #[test]
+    fn test_validate_commands() {
+        let spec = CommandSpec::parse(r#"{ "name": "x", "commands": ["moc"] }"#).unwrap();
+        assert_eq!(spec.commands, vec!["moc"]);
+
+        let err = CommandSpec::parse(r#"{ "name": "x", "commands": ["moc", "moc"] }"#).unwrap_err();
+        assert!(matches!(err, SpecError::InvalidCommand { .. }));
+
+        let err = CommandSpec::parse(r#"{ "name": "x", "commands": [""] }"#).unwrap_err();
+        assert!(matches!(err, SpecError::InvalidCommand { .. }));
+    }
+
+    #[test]
+    fn test_validate_registers() {
+        let spec =
+            CommandSpec::parse(r#"{ "name": "x", "registers": ["icp:build/registry"] }"#).unwrap();
+
+        assert!(spec.registers_through("icp:build/registry@0.6.13"));
+        assert!(spec.registers_through("icp:build/registry"));
+        assert!(!spec.registers_through("icp:build/lib@0.6.13"));
+
+        for registers in [r#"[""]"#, r#"["a/b@1.0.0"]"#, r#"["a/b", "a/b"]"#] {
+            let err =
+                CommandSpec::parse(&format!(r#"{{ "name": "x", "registers": {registers} }}"#))
+                    .unwrap_err();
+
+            assert!(matches!(err, SpecError::InvalidRegistration { .. }));
+        }
+    }
+
+    #[test]
+    fn test_parse_long_help_and_examples() {
+        let spec = CommandSpec::parse(
+            r#"{
+                "name": "build",
+                "long_help": "Builds all canisters.",
+                "args": [{ "name": "canister", "long_help": "Only build this canister." }],
+                "examples": [{ "command": "icp project build", "help": "Build everything" }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(spec.long_help.as_deref(), Some("Builds all canisters."));
+        assert!(spec.args[0].long_help.is_some());
+        assert_eq!(spec.examples[0].command, "icp project build");
+
+        let err =
+            CommandSpec::parse(r#"{ "name": "x", "examples": [{ "command": " " }] }"#).unwrap_err();
+        assert!(matches!(err, SpecError::EmptyExample(_)));
+    }
+
+    #[test]
+    fn test_parse_schema_v2_args() {
+        let spec = CommandSpec::parse(
+            r#"{
+                "schema": 2,
+                "name": "build",
+                "aliases": ["b"],
+                "args": [
+                    { "name": "release", "long": "release", "flag": true, "env": "BUILD_RELEASE" },
+                    { "name": "canister", "long": "canister", "aliases": ["can"], "multiple": true },
+                    { "name": "mode", "long": "mode", "possible_values": ["debug", "release"], "default": "debug" },
+                    { "name": "internal", "long": "internal", "hidden": true },
+                    { "name": "paths", "multiple": true }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(spec.schema, Some(2));
+        assert!(spec.args[0].flag);
+        assert!(spec.args[1].multiple);
+        assert_eq!(spec.args[2].default.as_deref(), Some("debug"));
+
+        // Schema 1 specs (without `schema`) parse with the new fields unset
+        let spec = CommandSpec::parse(r#"{ "name": "x", "args": [{ "name": "a" }] }"#).unwrap();
+        assert_eq!(spec.schema, None);
+        assert!(!spec.args[0].flag && !spec.args[0].multiple);
+
+        let err = CommandSpec::parse(r#"{ "schema": 99, "name": "x" }"#).unwrap_err();
+        assert!(matches!(err, SpecError::UnsupportedSchema(99)));
+    }
+
+    #[test]
+    fn test_validate_invalid_args() {
+        for args in [
+            r#"{ "name": "a", "flag": true }"#,
+            r#"{ "name": "a", "long": "a", "flag": true, "multiple": true }"#,
+            r#"{ "name": "a", "long": "a", "default": "x", "possible_values": ["y"] }"#,
+            r#"{ "name": "a", "aliases": ["b"] }"#,
+            r#"{ "name": "a", "multiple": true }, { "name": "b" }"#,
+        ] {
+            let err =
+                CommandSpec::parse(&format!(r#"{{ "name": "x", "args": [{args}] }}"#)).unwrap_err();
+
+            assert!(matches!(err, SpecError::InvalidArg { .. }), "{args}: {err}");
+        }
+
+        let err = CommandSpec::parse(
+            r#"{ "name": "x", "args": [{ "name": "a", "long": "a" }, { "name": "b", "long": "b", "aliases": ["a"] }] }"#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, SpecError::DuplicateFlag { .. }));
+
+        let err = CommandSpec::parse(
+            r#"{ "name": "x", "subcommands": [{ "name": "y" }, { "name": "z", "aliases": ["y"] }] }"#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, SpecError::DuplicateSubcommand { .. }));
+    }
+
+    #[test]
+    fn test_validate_nested_subcommands() {
+        let err = CommandSpec::parse(
+            r#"{ "name": "x", "subcommands": [{ "name": "y", "subcommands": [{ "name": "z" }, { "name": "z" }] }] }"#,
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, SpecError::DuplicateSubcommand { .. }));
+    }
+}
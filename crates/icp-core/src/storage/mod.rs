@@ -0,0 +1,279 @@
+//! Persistent key-value storage for icp extensions.
+//!
+//! Each extension gets its own namespace: a directory, named after the extension, under the
+//! storage root. Every key is stored in its own file, whose name is an escaped form of the key,
+//! so keys cannot address files outside of the namespace. Uppercase letters are escaped as well,
+//! so keys differing only in case do not collide on case-insensitive filesystems. Values are written to a temporary
+//! file which is then renamed over the previous one, so a value is never partially written.
+
+use std::{
+    fs::{create_dir_all, read, read_dir, remove_file, rename, write},
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use thiserror::Error;
+
+/// Prefix of temporary files, which never clashes with an escaped key
+const TEMP_PREFIX: &str = ".tmp-";
+
+/// Maximum length of an escaped key, the file name limit of common filesystems
+const MAX_NAME_LEN: usize = 255;
+
+/// Errors that can occur while accessing storage
+#[derive(Debug, Error)]
+pub enum StorageError {
+    /// The key is empty
+    #[error("invalid key: keys must not be empty")]
+    InvalidKey,
+
+    /// The escaped key does not fit in a file name
+    #[error("invalid key '{0}': keys must be at most {MAX_NAME_LEN} bytes once escaped")]
+    KeyTooLong(String),
+
+    /// The underlying filesystem operation failed
+    #[error("storage operation failed for key '{key}': {err}")]
+    Io {
+        /// The key being accessed (empty when listing keys)
+        key: String,
+
+        /// The underlying error
+        #[source]
+        err: io::Error,
+    },
+}
+
+/// Key-value storage namespace of a single extension
+#[derive(Debug, Clone)]
+pub struct Storage {
+    /// Directory holding the namespace's values
+    dir: PathBuf,
+}
+
+impl Storage {
+    /// Create the storage namespace of an extension (the directory is created on first write)
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - Directory holding the namespaces of all extensions
+    /// * `extension` - Name of the extension
+    pub fn new(root: &Path, extension: &str) -> Self {
+        Self {
+            dir: root.join(escape(extension)),
+        }
+    }
+
+    /// Get the value stored under a key, if any
+    pub fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        match read(self.path(key)?) {
+            Ok(v) => Ok(Some(v)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(io_error(key, err)),
+        }
+    }
+
+    /// Store a value under a key, replacing the previous one atomically
+    pub fn set(&self, key: &str, value: &[u8]) -> Result<(), StorageError> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let p = self.path(key)?;
+
+        create_dir_all(&self.dir).map_err(|err| io_error(key, err))?;
+
+        // Write to a temporary file first, unique to this process and write
+        let tmp = self.dir.join(format!(
+            "{TEMP_PREFIX}{}-{}",
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        write(&tmp, value)
+            .and_then(|_| rename(&tmp, &p))
+            .map_err(|err| {
+                let _ = remove_file(&tmp);
+                io_error(key, err)
+            })
+    }
+
+    /// Delete the value stored under a key, returning whether there was one
+    pub fn delete(&self, key: &str) -> Result<bool, StorageError> {
+        match remove_file(self.path(key)?) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(io_error(key, err)),
+        }
+    }
+
+    /// List the keys in the namespace, sorted
+    pub fn list_keys(&self) -> Result<Vec<String>, StorageError> {
+        let entries = match read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(io_error("", err)),
+        };
+
+        let mut keys = vec![];
+
+        for entry in entries {
+            let entry = entry.map_err(|err| io_error("", err))?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+
+            if let Some(key) = unescape(&name) {
+                keys.push(key);
+            }
+        }
+
+        keys.sort();
+
+        Ok(keys)
+    }
+
+    /// Path of the file holding the value of a key
+    fn path(&self, key: &str) -> Result<PathBuf, StorageError> {
+        if key.is_empty() {
+            return Err(StorageError::InvalidKey);
+        }
+
+        let name = escape(key);
+        if name.len() > MAX_NAME_LEN {
+            return Err(StorageError::KeyTooLong(key.to_owned()));
+        }
+
+        Ok(self.dir.join(name))
+    }
+}
+
+fn io_error(key: &str, err: io::Error) -> StorageError {
+    StorageError::Io {
+        key: key.to_owned(),
+        err,
+    }
+}
+
+/// Escape a key into a file name, keeping only lowercase letters, digits, `-` and `_` as they are
+fn escape(key: &str) -> String {
+    key.bytes()
+        .map(|b| match b {
+            b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' => (b as char).to_string(),
+            b => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// Recover a key from a file name, or `None` if it is not an escaped key (e.g. a temporary file)
+fn unescape(name: &str) -> Option<String> {
+    let mut out = vec![];
+    let mut bs = name.bytes();
+
+    while let Some(b) = bs.next() {
+        match b {
+            b'%' => {
+                let hex = [bs.next()?, bs.next()?];
+                out.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+            }
+            b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' => out.push(b),
+            _ => return None,
+        }
+    }
+
+    String::from_utf8(out).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_set_get_delete() {
+        let tmp = TempDir::new().unwrap();
+        let s = Storage::new(tmp.path(), "identity");
+
+        assert_eq!(s.get("default").unwrap(), None);
+
+        s.set("default", b"one").unwrap();
+        s.set("default", b"two").unwrap();
+        assert_eq!(s.get("default").unwrap(), Some(b"two".to_vec()));
+
+        assert!(s.delete("default").unwrap());
+        assert!(!s.delete("default").unwrap());
+        assert_eq!(s.get("default").unwrap(), None);
+
+        assert!(matches!(s.set("", b""), Err(StorageError::InvalidKey)));
+    }
+
+    #[test]
+    fn test_list_keys() {
+        let tmp = TempDir::new().unwrap();
+        let s = Storage::new(tmp.path(), "build");
+
+        assert_eq!(s.list_keys().unwrap(), Vec::<String>::new());
+
+        for k in ["motoko", "Motoko", "a/b", "../escape", "ключ"] {
+            s.set(k, b"").unwrap();
+        }
+
+        // Leftover temporary files are ignored
+        write(s.dir.join(format!("{TEMP_PREFIX}1-0")), "").unwrap();
+
+        assert_eq!(
+            s.list_keys().unwrap(),
+            vec!["../escape", "Motoko", "a/b", "motoko", "ключ"]
+        );
+
+        // Keys never address files outside of the namespace
+        assert_eq!(read_dir(tmp.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_keys_differing_in_case() {
+        let tmp = TempDir::new().unwrap();
+        let s = Storage::new(tmp.path(), "identity");
+
+        s.set("Foo", b"upper").unwrap();
+        s.set("foo", b"lower").unwrap();
+
+        assert_eq!(s.get("Foo").unwrap(), Some(b"upper".to_vec()));
+        assert_eq!(s.get("foo").unwrap(), Some(b"lower".to_vec()));
+        assert_eq!(s.list_keys().unwrap(), vec!["Foo", "foo"]);
+
+        // File names never differ in case only
+        let mut names: Vec<String> = read_dir(&s.dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_lowercase())
+            .collect();
+
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), 2);
+    }
+
+    #[test]
+    fn test_key_too_long() {
+        let tmp = TempDir::new().unwrap();
+        let s = Storage::new(tmp.path(), "identity");
+
+        s.set(&"k".repeat(MAX_NAME_LEN), b"").unwrap();
+
+        // Each uppercase letter takes three bytes once escaped
+        let key = "K".repeat(MAX_NAME_LEN / 3 + 1);
+
+        assert!(matches!(s.set(&key, b""), Err(StorageError::KeyTooLong(k)) if k == key));
+        assert!(matches!(s.get(&key), Err(StorageError::KeyTooLong(_))));
+    }
+
+    #[test]
+    fn test_namespaces_are_isolated() {
+        let tmp = TempDir::new().unwrap();
+        let a = Storage::new(tmp.path(), "a");
+        let b = Storage::new(tmp.path(), "b");
+
+        a.set("key", b"a").unwrap();
+
+        assert_eq!(b.get("key").unwrap(), None);
+        assert_eq!(b.list_keys().unwrap(), Vec::<String>::new());
+    }
+}
@@ -50,7 +50,7 @@ struct Args {
 // Updated function to parse extensions directly from JSON string
 fn parse_extensions_from_json(json_string: &str) -> Result<Vec<ExtensionAsset>> {
     let inputs: Vec<ExtensionInfoInput> = serde_json::from_str(json_string)
-        .map_err(|e| icp_distribution::DistributionError::JsonError(e))?; // Handle JSON parsing error
+        .map_err(icp_distribution::DistributionError::JsonError)?; // Handle JSON parsing error
 
     Ok(inputs
         .into_iter()
@@ -83,7 +83,7 @@ fn main() -> Result<()> {
 
     // Read extension info JSON from the specified file path
     let extension_json_content = std::fs::read_to_string(&args.extension_info_path)
-        .map_err(|e| icp_distribution::DistributionError::IoError(e))?; // Handle file reading error
+        .map_err(icp_distribution::DistributionError::IoError)?; // Handle file reading error
     println!("Read extension info from: {:?}", args.extension_info_path);
 
     // Parse extensions from the JSON content read from the file
@@ -0,0 +1,116 @@
+//! Registry Index Generator Tool
+//!
+//! A CLI tool to add the latest extension releases to the registry index used by
+//! `icp extension search` and `icp extension install`.
+
+use clap::Parser;
+use icp_distribution::{
+    component_interfaces, DistributionError, RegistryIndex, RegistryVersion, Result,
+};
+use semver::Version;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+// Define a struct that matches the JSON structure from fetch-latest-extensions
+#[derive(Deserialize, Debug)]
+struct ExtensionInfoInput {
+    name: String,
+    version: Version,
+    url: String,
+    sha256: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Parser)]
+#[command(about = "Add extension releases to the registry index")]
+struct Args {
+    /// Path to the JSON file containing extension info (name, version, url, sha256)
+    #[arg(long)]
+    extension_info_path: PathBuf,
+
+    /// Directory containing the released components (<name>.component.wasm), used to
+    /// verify their checksums and declare their interfaces
+    #[arg(long)]
+    extensions_dir: Option<PathBuf>,
+
+    /// Path to the previously published index, whose versions are kept
+    #[arg(long)]
+    index: Option<PathBuf>,
+
+    /// Output path for the generated index
+    #[arg(long, default_value = "dist/extensions/index.json")]
+    output: PathBuf,
+}
+
+fn run() -> Result<()> {
+    let args = Args::parse();
+
+    let mut index = match &args.index {
+        Some(p) => RegistryIndex::parse(&fs::read_to_string(p)?)?,
+        None => RegistryIndex::default(),
+    };
+
+    let inputs: Vec<ExtensionInfoInput> =
+        serde_json::from_str(&fs::read_to_string(&args.extension_info_path)?)?;
+
+    for input in inputs {
+        let (mut imports, mut exports) = (vec![], vec![]);
+
+        // Verify the released component and declare its interfaces
+        if let Some(dir) = &args.extensions_dir {
+            let p = dir.join(format!("{}.component.wasm", input.name));
+
+            if p.exists() {
+                let bs = fs::read(&p)?;
+
+                if format!("{:x}", Sha256::digest(&bs)) != input.sha256 {
+                    return Err(DistributionError::ChecksumMismatch(
+                        p.to_string_lossy().into_owned(),
+                    ));
+                }
+
+                (imports, exports) = component_interfaces(&bs)?;
+            } else {
+                eprintln!("Warning: Component not found for {}: {:?}", input.name, p);
+            }
+        }
+
+        println!("Adding {} {}", input.name, input.version);
+
+        index.publish(
+            &input.name,
+            input.description,
+            RegistryVersion {
+                version: input.version,
+                url: input.url,
+                sha256: input.sha256,
+                imports,
+                exports,
+            },
+        );
+    }
+
+    // Validate the result before publishing it
+    let content = serde_json::to_string_pretty(&index)?;
+    RegistryIndex::parse(&content)?;
+
+    if let Some(parent) = args.output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(&args.output, content)?;
+    println!("Registry index written to: {:?}", args.output);
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    }
+}
@@ -60,7 +60,7 @@ struct TemplateData {
 // Function to parse extensions directly from JSON string
 fn parse_extensions_from_json(json_string: &str) -> Result<Vec<ExtensionInfo>> {
     let inputs: Vec<ExtensionInfoInput> =
-        serde_json::from_str(json_string).map_err(|e| DistributionError::JsonError(e))?; // Handle JSON parsing error
+        serde_json::from_str(json_string).map_err(DistributionError::JsonError)?; // Handle JSON parsing error
 
     Ok(inputs
         .into_iter()
@@ -114,7 +114,7 @@ fn run() -> Result<()> {
     // Generate landing page
     // Read extension info JSON from the specified file path
     let extension_json_content =
-        fs::read_to_string(&args.extension_info_path).map_err(|e| DistributionError::IoError(e))?; // Handle file reading error
+        fs::read_to_string(&args.extension_info_path).map_err(DistributionError::IoError)?; // Handle file reading error
     println!("Read extension info from: {:?}", args.extension_info_path);
 
     // Parse extensions from the JSON content read from the file
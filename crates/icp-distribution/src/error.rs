@@ -35,6 +35,12 @@ pub enum DistributionError {
 
     #[error("JSON error: {0}")]
     JsonError(#[from] serde_json::Error),
+
+    #[error("Invalid registry index: {0}")]
+    InvalidIndex(String),
+
+    #[error("Invalid version requirement: {0}")]
+    InvalidVersion(String),
 }
 
 pub type Result<T> = std::result::Result<T, DistributionError>;
@@ -1,16 +1,22 @@
 //! ICP Distribution Library
 //!
 //! This library provides functionality for managing ICP CLI binary distribution,
-//! including binary validation, URL management, and template rendering.
+//! including binary validation, URL management, template rendering, and the registry
+//! index listing published extensions.
 
 mod binary;
 mod distribution;
 mod error;
+mod registry;
 mod url;
 
 pub use binary::{BinaryInfo, BinaryProcessor, ExtensionInfo};
 pub use distribution::Distribution;
 pub use error::{DistributionError, Result};
+pub use registry::{
+    component_interfaces, parse_version_req, resolve_url, RegistryExtension, RegistryIndex,
+    RegistryVersion, DEFAULT_INDEX_URL, INDEX_SCHEMA,
+};
 pub use url::UrlBuilder;
 
 use serde::Serialize;
@@ -73,7 +79,7 @@ pub fn render_template(
     // Render template
     let rendered = handlebars
         .render(name, data)
-        .map_err(|e| DistributionError::TemplateError(e))?;
+        .map_err(DistributionError::TemplateError)?;
 
     // Ensure parent directory exists
     if let Some(parent) = output_path.parent() {
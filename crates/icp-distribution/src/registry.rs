@@ -0,0 +1,382 @@
+use crate::error::{DistributionError, Result};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use wasmparser::{ComponentExternalKind, ComponentTypeRef, Parser, Payload};
+
+/// Version of the registry index format
+pub const INDEX_SCHEMA: u32 = 1;
+
+/// Location of the published registry index
+pub const DEFAULT_INDEX_URL: &str = "https://rikonor.github.io/icp-cli/extensions/index.json";
+
+/// Registry index listing the published versions of extensions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryIndex {
+    /// Version of the index format
+    pub schema: u32,
+
+    /// Published extensions, sorted by name
+    pub extensions: Vec<RegistryExtension>,
+}
+
+/// Extension published in a registry index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryExtension {
+    /// Name of the extension
+    pub name: String,
+
+    /// Human-readable description of the extension
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Published versions, newest first
+    pub versions: Vec<RegistryVersion>,
+}
+
+/// Published version of an extension
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryVersion {
+    /// Semantic version
+    pub version: Version,
+
+    /// Location of the component, either absolute or relative to the index
+    pub url: String,
+
+    /// SHA256 checksum of the component
+    pub sha256: String,
+
+    /// Interfaces imported by the component
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<String>,
+
+    /// Interfaces exported by the component
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exports: Vec<String>,
+}
+
+impl Default for RegistryIndex {
+    fn default() -> Self {
+        Self {
+            schema: INDEX_SCHEMA,
+            extensions: vec![],
+        }
+    }
+}
+
+impl RegistryIndex {
+    /// Parses and validates a registry index
+    pub fn parse(s: &str) -> Result<Self> {
+        let idx: Self = serde_json::from_str(s)?;
+
+        if idx.schema != INDEX_SCHEMA {
+            return Err(DistributionError::InvalidIndex(format!(
+                "unsupported schema version {} (expected {INDEX_SCHEMA})",
+                idx.schema
+            )));
+        }
+
+        for (i, x) in idx.extensions.iter().enumerate() {
+            if idx.extensions[..i].iter().any(|other| other.name == x.name) {
+                return Err(DistributionError::InvalidIndex(format!(
+                    "duplicate extension {}",
+                    x.name
+                )));
+            }
+
+            for (j, v) in x.versions.iter().enumerate() {
+                if x.versions[..j]
+                    .iter()
+                    .any(|other| other.version == v.version)
+                {
+                    return Err(DistributionError::InvalidIndex(format!(
+                        "duplicate version {} of extension {}",
+                        v.version, x.name
+                    )));
+                }
+
+                if v.sha256.len() != 64 || !v.sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(DistributionError::InvalidIndex(format!(
+                        "invalid sha256 for version {} of extension {}",
+                        v.version, x.name
+                    )));
+                }
+            }
+        }
+
+        Ok(idx)
+    }
+
+    /// Finds an extension by name
+    pub fn get(&self, name: &str) -> Option<&RegistryExtension> {
+        self.extensions.iter().find(|x| x.name == name)
+    }
+
+    /// Lists the extensions whose name or description contains the query (case-insensitive)
+    pub fn search(&self, query: &str) -> Vec<&RegistryExtension> {
+        let query = query.to_lowercase();
+
+        self.extensions
+            .iter()
+            .filter(|x| {
+                x.name.to_lowercase().contains(&query)
+                    || x.description
+                        .as_ref()
+                        .is_some_and(|d| d.to_lowercase().contains(&query))
+            })
+            .collect()
+    }
+
+    /// Adds a version of an extension, replacing any previously published entry for that version
+    pub fn publish(&mut self, name: &str, description: Option<String>, v: RegistryVersion) {
+        let idx = match self.extensions.iter().position(|x| x.name == name) {
+            Some(idx) => idx,
+            None => {
+                self.extensions.push(RegistryExtension {
+                    name: name.to_owned(),
+                    description: None,
+                    versions: vec![],
+                });
+
+                self.extensions.len() - 1
+            }
+        };
+
+        let x = &mut self.extensions[idx];
+
+        if description.is_some() {
+            x.description = description;
+        }
+
+        x.versions.retain(|other| other.version != v.version);
+        x.versions.push(v);
+        x.versions.sort_by(|a, b| b.version.cmp(&a.version));
+
+        self.extensions.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+}
+
+impl RegistryExtension {
+    /// Resolves the newest version matching a requirement
+    ///
+    /// Without a requirement, the newest version that is not a pre-release is resolved.
+    pub fn resolve(&self, req: Option<&VersionReq>) -> Option<&RegistryVersion> {
+        self.versions
+            .iter()
+            .filter(|v| match req {
+                Some(req) => req.matches(&v.version),
+                None => v.version.pre.is_empty(),
+            })
+            .max_by(|a, b| a.version.cmp(&b.version))
+    }
+
+    /// Newest version of the extension that is not a pre-release
+    pub fn latest(&self) -> Option<&RegistryVersion> {
+        self.resolve(None)
+    }
+}
+
+/// Parses a version requirement as typed by users, where a full version (e.g. `1.2.0`)
+/// requires exactly that version and a partial one (e.g. `1.2`) any version starting with it,
+/// unlike Cargo where they mean `^1.2.0` and `^1.2`
+pub fn parse_version_req(s: &str) -> Result<VersionReq> {
+    let s = s.trim();
+
+    let req = match Version::parse(s) {
+        Ok(_) => VersionReq::parse(&format!("={s}")),
+        Err(_) if s.starts_with(|c: char| c.is_ascii_digit()) => {
+            VersionReq::parse(&format!("~{s}"))
+        }
+        Err(_) => VersionReq::parse(s),
+    };
+
+    req.map_err(|err| DistributionError::InvalidVersion(format!("{s}: {err}")))
+}
+
+/// Resolves the location of a component against the location of the index it is listed in
+pub fn resolve_url(index_url: &str, url: &str) -> String {
+    if url.contains("://") || url.starts_with('/') {
+        return url.to_owned();
+    }
+
+    match index_url.rsplit_once('/') {
+        Some((base, _)) => format!("{base}/{url}"),
+        None => url.to_owned(),
+    }
+}
+
+/// Lists the interfaces imported and exported by a component (e.g. `icp:cli/misc@0.5.0`)
+pub fn component_interfaces(bs: &[u8]) -> Result<(Vec<String>, Vec<String>)> {
+    let (mut imports, mut exports) = (vec![], vec![]);
+
+    // Nesting level of the module or component being parsed (1 for the component itself)
+    let mut depth = 0;
+
+    for payload in Parser::new(0).parse_all(bs) {
+        let payload = payload.map_err(|err| DistributionError::InvalidFormat(err.to_string()))?;
+
+        match payload {
+            Payload::Version { .. } => depth += 1,
+            Payload::End(_) => depth -= 1,
+
+            Payload::ComponentImportSection(r) if depth == 1 => {
+                for imp in r {
+                    let imp =
+                        imp.map_err(|err| DistributionError::InvalidFormat(err.to_string()))?;
+
+                    if let ComponentTypeRef::Instance(_) = imp.ty {
+                        imports.push(imp.name.0.to_owned());
+                    }
+                }
+            }
+
+            Payload::ComponentExportSection(r) if depth == 1 => {
+                for exp in r {
+                    let exp =
+                        exp.map_err(|err| DistributionError::InvalidFormat(err.to_string()))?;
+
+                    if let ComponentExternalKind::Instance = exp.kind {
+                        exports.push(exp.name.0.to_owned());
+                    }
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    Ok((imports, exports))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    fn version(v: &str) -> RegistryVersion {
+        RegistryVersion {
+            version: Version::parse(v).unwrap(),
+            url: format!("build-{v}.component.wasm"),
+            sha256: SHA256.to_string(),
+            imports: vec![],
+            exports: vec![],
+        }
+    }
+
+    fn index() -> RegistryIndex {
+        let mut idx = RegistryIndex::default();
+
+        for v in ["0.1.0", "0.2.0", "0.2.1", "0.3.0-beta.1"] {
+            idx.publish("build", Some("Build canisters".to_string()), version(v));
+        }
+
+        idx.publish("identity", None, version("1.0.0"));
+        idx
+    }
+
+    #[test]
+    fn test_parse_roundtrip() {
+        let idx = index();
+        let s = serde_json::to_string(&idx).unwrap();
+
+        assert_eq!(RegistryIndex::parse(&s).unwrap(), idx);
+        assert_eq!(
+            idx.extensions[0].versions[0].version.to_string(),
+            "0.3.0-beta.1"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let mut idx = index();
+        idx.schema = 2;
+        assert!(matches!(
+            RegistryIndex::parse(&serde_json::to_string(&idx).unwrap()),
+            Err(DistributionError::InvalidIndex(_))
+        ));
+
+        let mut idx = index();
+        idx.extensions[1].versions[0].sha256 = "abc".to_string();
+        assert!(matches!(
+            RegistryIndex::parse(&serde_json::to_string(&idx).unwrap()),
+            Err(DistributionError::InvalidIndex(_))
+        ));
+
+        let mut idx = index();
+        let dup = idx.extensions[1].clone();
+        idx.extensions.push(dup);
+        assert!(matches!(
+            RegistryIndex::parse(&serde_json::to_string(&idx).unwrap()),
+            Err(DistributionError::InvalidIndex(_))
+        ));
+
+        let s = r#"{ "schema": 1, "extensions": [{ "name": "x", "versions": [{ "version": "1", "url": "x", "sha256": "" }] }] }"#;
+        assert!(matches!(
+            RegistryIndex::parse(s),
+            Err(DistributionError::JsonError(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let idx = index();
+        let x = idx.get("build").unwrap();
+
+        let resolve = |req: Option<&str>| {
+            let req = req.map(|r| parse_version_req(r).unwrap());
+            x.resolve(req.as_ref()).map(|v| v.version.to_string())
+        };
+
+        assert_eq!(resolve(None), Some("0.2.1".to_string()));
+        assert_eq!(resolve(Some("0.2.0")), Some("0.2.0".to_string()));
+        assert_eq!(resolve(Some("0.1")), Some("0.1.0".to_string()));
+        assert_eq!(resolve(Some("^0.2")), Some("0.2.1".to_string()));
+        assert_eq!(
+            resolve(Some("=0.3.0-beta.1")),
+            Some("0.3.0-beta.1".to_string())
+        );
+        assert_eq!(resolve(Some(">=1")), None);
+        assert!(matches!(
+            parse_version_req("latest"),
+            Err(DistributionError::InvalidVersion(_))
+        ));
+    }
+
+    #[test]
+    fn test_search() {
+        let idx = index();
+        let names = |q| {
+            idx.search(q)
+                .iter()
+                .map(|x| x.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names("CANISTER"), vec!["build"]);
+        assert_eq!(names("ident"), vec!["identity"]);
+        assert_eq!(names(""), vec!["build", "identity"]);
+    }
+
+    #[test]
+    fn test_resolve_url() {
+        assert_eq!(
+            resolve_url("file:///srv/registry/index.json", "build.component.wasm"),
+            "file:///srv/registry/build.component.wasm"
+        );
+        assert_eq!(
+            resolve_url(
+                "http://localhost:8000/index.json",
+                "https://example.com/build.wasm"
+            ),
+            "https://example.com/build.wasm"
+        );
+    }
+
+    #[test]
+    fn test_component_interfaces_invalid() {
+        assert!(matches!(
+            component_interfaces(b"not wasm"),
+            Err(DistributionError::InvalidFormat(_))
+        ));
+    }
+}